@@ -0,0 +1,41 @@
+//! Throughput of `make_utf8_umlauts_uppercase` compared to uppercasing byte by byte.
+//!
+//! ```sh
+//! cargo bench --bench uppercase
+//! ```
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use umlauts::make_utf8_umlauts_uppercase_scalar;
+use umlauts::prelude::*;
+
+fn bench(name: &str, text: &str, uppercase: fn(&mut [u8])) {
+    let pristine = text.repeat(10_000).into_bytes();
+    let mut bytes = pristine.clone();
+    let rounds = 100;
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..rounds {
+        // every round uppercases the original text, the copy is not timed
+        bytes.copy_from_slice(&pristine);
+        let start = Instant::now();
+        uppercase(black_box(&mut bytes));
+        elapsed += start.elapsed();
+    }
+    let mib = (bytes.len() * rounds) as f64 / (1024.0 * 1024.0);
+    println!("{:<20} {:>8.1} MiB/s", name, mib / elapsed.as_secs_f64());
+}
+
+fn main() {
+    let texts = [
+        ("ascii", "Max Mustermann, Hauptstrasse 1, 12345 Berlin\n"),
+        ("names", "Jürgen Müller, Königstraße 7, 50667 Köln\n"),
+        ("umlauts", "äöüäöüäöüäöüäöüäöü\n"),
+    ];
+    for (name, text) in texts.iter() {
+        bench(&format!("{} chunked", name), text, |bytes| {
+            bytes.make_utf8_umlauts_uppercase()
+        });
+        bench(&format!("{} scalar", name), text, |bytes| {
+            make_utf8_umlauts_uppercase_scalar(bytes, 0)
+        });
+    }
+}
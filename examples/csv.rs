@@ -0,0 +1,27 @@
+//! Folds the umlauts of selected columns of a CSV file.
+//!
+//! ```sh
+//! cargo run --example csv -- ';' 1 2 < export.csv
+//! ```
+use std::env::args;
+use std::io::{stdin, stdout, BufRead, Write};
+use umlauts::{fold_delimited, DelimitedStyle};
+
+fn main() -> std::io::Result<()> {
+    let mut args = args().skip(1);
+    let delimiter = args
+        .next()
+        .and_then(|delimiter| delimiter.bytes().next())
+        .unwrap_or(b',');
+    let columns: Vec<usize> = args.filter_map(|column| column.parse().ok()).collect();
+    let style = DelimitedStyle::new();
+
+    let stdout = stdout();
+    let mut out = stdout.lock();
+    for line in stdin().lock().split(b'\n') {
+        out.write_all(&fold_delimited(&line?, delimiter, &columns, &style))?;
+        out.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
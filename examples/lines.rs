@@ -1,12 +1,12 @@
-use std::io::{stdin, BufRead};
-use umlauts::prelude::*;
+use std::io::{stdin, stdout, Write};
+use umlauts::{folded_lines, Transliterator};
 
 fn main() -> std::io::Result<()> {
-    for line in stdin().lock().lines() {
-        let mut line = line?.into_bytes();
-        line.make_utf8_umlauts_to_ascii();
-        println!("{}", String::from_utf8(line).unwrap());
+    let stdout = stdout();
+    let mut out = stdout.lock();
+    for line in folded_lines(stdin().lock(), Transliterator::new()) {
+        writeln!(out, "{}", line?)?;
     }
 
     Ok(())
-}
\ No newline at end of file
+}
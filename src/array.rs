@@ -0,0 +1,126 @@
+//! Folding into fixed size buffers.
+use std::error::Error;
+use std::fmt;
+
+use crate::fold::next_umlaut;
+
+/// Error returned if the folded text does not fit into a fixed size buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    required: usize,
+    capacity: usize,
+}
+
+impl CapacityError {
+    /// Returns the number of bytes the folded text needs.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+
+    /// Returns the size of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "folded text needs {} bytes, but the buffer holds only {}",
+            self.required, self.capacity
+        )
+    }
+}
+
+impl Error for CapacityError {}
+
+/// Writes `bytes` folded according to DIN 5007-2 to the start of `out`.
+///
+/// See [`UmlautsOwned::fold_ascii_into_array`](crate::UmlautsOwned::fold_ascii_into_array).
+pub(crate) fn fold_into_slice(bytes: &[u8], out: &mut [u8]) -> Result<usize, CapacityError> {
+    let mut written = 0;
+    let mut last = 0;
+    let mut write = |part: &[u8], written: &mut usize| {
+        if let Some(dest) = out.get_mut(*written..*written + part.len()) {
+            dest.copy_from_slice(part);
+        }
+        *written += part.len();
+    };
+    while let Some((range, replacement)) = next_umlaut(bytes, last) {
+        write(&bytes[last..range.start], &mut written);
+        write(replacement, &mut written);
+        last = range.end;
+    }
+    write(&bytes[last..], &mut written);
+
+    if written <= out.len() {
+        Ok(written)
+    } else {
+        Err(CapacityError {
+            required: written,
+            capacity: out.len(),
+        })
+    }
+}
+
+/// Returns the length of `bytes` folded according to DIN 5007-2.
+#[cfg(feature = "bumpalo")]
+pub(crate) fn folded_len(bytes: &[u8]) -> usize {
+    let mut len = bytes.len();
+    let mut last = 0;
+    while let Some((range, replacement)) = next_umlaut(bytes, last) {
+        len = len - range.len() + replacement.len();
+        last = range.end;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fold_into_slice, CapacityError};
+
+    #[test]
+    #[cfg(feature = "bumpalo")]
+    fn lengths() {
+        use super::folded_len;
+
+        assert_eq!(folded_len("Grüße".as_bytes()), 7);
+        assert_eq!(folded_len("GROẞ".as_bytes()), 5);
+        assert_eq!(folded_len(b""), 0);
+    }
+
+    #[test]
+    fn fits() {
+        let mut out = [0; 8];
+        assert_eq!(fold_into_slice("Grüße".as_bytes(), &mut out), Ok(7));
+        assert_eq!(&out[..7], b"Gruesse");
+        assert_eq!(fold_into_slice("ẞ".as_bytes(), &mut out[..2]), Ok(2));
+        assert_eq!(fold_into_slice(b"", &mut []), Ok(0));
+    }
+
+    #[test]
+    fn overflows() {
+        let text = "ßßßß".as_bytes();
+        let mut out = [0; 8];
+        assert_eq!(
+            fold_into_slice(text, &mut out[..7]),
+            Err(CapacityError {
+                required: 8,
+                capacity: 7,
+            })
+        );
+        assert_eq!(
+            fold_into_slice("ẞ x".as_bytes(), &mut out[..3])
+                .unwrap_err()
+                .required(),
+            4
+        );
+        assert_eq!(
+            fold_into_slice("Straße".as_bytes(), &mut [])
+                .unwrap_err()
+                .to_string(),
+            "folded text needs 7 bytes, but the buffer holds only 0"
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Folding of asynchronous readers.
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::UmlautFoldState;
+
+const CHUNK_SIZE: usize = 4096;
+
+/// Asynchronous reader folding umlauts according to DIN 5007-2.
+///
+/// Wraps an [`AsyncRead`] and yields its data with all umlauts replaced,
+/// umlauts split between two reads are handled by [`UmlautFoldState`].
+///
+/// Requires the `tokio` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use tokio::io::AsyncReadExt;
+/// use umlauts::UmlautAsciiAsyncReader;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut reader = UmlautAsciiAsyncReader::new("Grüße".as_bytes());
+/// let mut folded = String::new();
+/// reader.read_to_string(&mut folded).await.unwrap();
+/// assert_eq!(folded, "Gruesse");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct UmlautAsciiAsyncReader<R> {
+    inner: R,
+    state: UmlautFoldState,
+    folded: Vec<u8>,
+    pos: usize,
+    eof: bool,
+}
+
+impl<R: AsyncRead + Unpin> UmlautAsciiAsyncReader<R> {
+    /// Creates a new reader folding the data of `inner`.
+    pub fn new(inner: R) -> UmlautAsciiAsyncReader<R> {
+        UmlautAsciiAsyncReader {
+            inner,
+            state: UmlautFoldState::new(),
+            folded: Vec::new(),
+            pos: 0,
+            eof: false,
+        }
+    }
+
+    /// Unwraps this reader, returning the underlying reader.
+    ///
+    /// Folded data that has not been read yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for UmlautAsciiAsyncReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if this.pos < this.folded.len() {
+                let len = buf.remaining().min(this.folded.len() - this.pos);
+                buf.put_slice(&this.folded[this.pos..this.pos + len]);
+                this.pos += len;
+                return Poll::Ready(Ok(()));
+            }
+            if this.eof {
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut chunk = [0; CHUNK_SIZE];
+            let mut chunk = ReadBuf::new(&mut chunk);
+            match Pin::new(&mut this.inner).poll_read(cx, &mut chunk) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+            this.folded.clear();
+            this.pos = 0;
+            if chunk.filled().is_empty() {
+                this.state.finish(&mut this.folded);
+                this.eof = true;
+            } else {
+                this.state.push(chunk.filled(), &mut this.folded);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
+
+    use super::UmlautAsciiAsyncReader;
+
+    #[tokio::test]
+    async fn duplex_byte_by_byte() {
+        let (mut client, server) = duplex(8);
+        let text = "Öl Ärmel Übermut, GROẞE Straße".as_bytes();
+
+        let write = async move {
+            for byte in text.chunks(1) {
+                client.write_all(byte).await.unwrap();
+            }
+        };
+        let read = async move {
+            let mut folded = Vec::new();
+            UmlautAsciiAsyncReader::new(server)
+                .read_to_end(&mut folded)
+                .await
+                .unwrap();
+            folded
+        };
+        let ((), folded) = tokio::join!(write, read);
+        assert_eq!(folded, b"Oel Aermel Uebermut, GROSSE Strasse");
+    }
+
+    #[tokio::test]
+    async fn trailing_lead_byte() {
+        let mut folded = Vec::new();
+        UmlautAsciiAsyncReader::new(&[b'a', 0xc3][..])
+            .read_to_end(&mut folded)
+            .await
+            .unwrap();
+        assert_eq!(folded, [b'a', 0xc3]);
+    }
+}
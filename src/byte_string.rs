@@ -0,0 +1,29 @@
+//! Tests for the byte strings of the `bstr` crate.
+//!
+//! `BStr` and `BString` dereference to `[u8]` and `Vec<u8>`, so the methods of
+//! `UmlautsInplaceExt`, `UmlautsOwned` and `UmlautsVecExt` are available on them without
+//! any forwarding impls.
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bstr::{BStr, BString, ByteSlice};
+
+    #[test]
+    fn bstring_round_trip() {
+        let mut text = BString::from("GROẞE Grüße");
+        text.fold_ascii_reusing_capacity();
+        assert_eq!(text, "GROSSE Gruesse");
+
+        let mut text = BString::from("Öl und Äpfel");
+        text.make_utf8_umlauts_to_lowercase_ascii();
+        assert_eq!(text.to_str(), Ok("oel und aepfel"));
+    }
+
+    #[test]
+    fn bstr_methods() {
+        let text = BStr::new("Grüße aus Köln");
+        assert_eq!(text.count_umlauts(), 3);
+        assert_eq!(text.fold_alnum_key(), b"gruesseauskoeln");
+        assert_eq!(text.fold_ascii_strict(), Ok(b"Gruesse aus Koeln".to_vec()));
+    }
+}
@@ -0,0 +1,163 @@
+//! UTF-8 encodings of the german special characters "äöüÄÖÜßẞ"
+//! and a classification of all byte values.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate umlauts;
+//! use umlauts::bytes::{A_DIAERESIS_LOWER, CAPITAL_SHARP_S};
+//!
+//! assert_eq!(&A_DIAERESIS_LOWER, "ä".as_bytes());
+//! assert!("GROẞ".as_bytes().ends_with(&CAPITAL_SHARP_S));
+//! ```
+
+/// 'ä' (U+00E4)
+pub const A_DIAERESIS_LOWER: [u8; 2] = [0xc3, 0xa4];
+/// 'ö' (U+00F6)
+pub const O_DIAERESIS_LOWER: [u8; 2] = [0xc3, 0xb6];
+/// 'ü' (U+00FC)
+pub const U_DIAERESIS_LOWER: [u8; 2] = [0xc3, 0xbc];
+/// 'Ä' (U+00C4)
+pub const A_DIAERESIS_UPPER: [u8; 2] = [0xc3, 0x84];
+/// 'Ö' (U+00D6)
+pub const O_DIAERESIS_UPPER: [u8; 2] = [0xc3, 0x96];
+/// 'Ü' (U+00DC)
+pub const U_DIAERESIS_UPPER: [u8; 2] = [0xc3, 0x9c];
+/// 'ß' (U+00DF)
+pub const SHARP_S: [u8; 2] = [0xc3, 0x9f];
+/// 'ẞ' (U+1E9E), the capital sharp S
+pub const CAPITAL_SHARP_S: [u8; 3] = [0xe1, 0xba, 0x9e];
+
+/// The role of a byte in UTF-8 encoded german text, see [`BYTE_CLASS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteClass {
+    /// An ASCII byte `0x00..=0x7f`, which is never part of an umlaut.
+    Ascii,
+    /// A continuation byte `0x80..=0xbf`.
+    Continuation,
+    /// `0xc3`, the lead byte of 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü' and 'ß', and of other
+    /// latin letters like 'é'.
+    UmlautLead,
+    /// A lead byte of another two byte sequence, `0xc2` or `0xc4..=0xdf`.
+    Lead2,
+    /// `0xe1`, the lead byte of 'ẞ' and of other three byte sequences.
+    CapitalSharpSLead,
+    /// A lead byte of another three byte sequence, `0xe0` or `0xe2..=0xef`.
+    Lead3,
+    /// A lead byte of a four byte sequence, `0xf0..=0xf4`.
+    Lead4,
+    /// A byte that never occurs in valid UTF-8, `0xc0`, `0xc1` and `0xf5..=0xff`.
+    Invalid,
+}
+
+/// The [`ByteClass`] of every byte value.
+///
+/// Indexing the table replaces a chain of range comparisons in hand written scanners.
+/// Only bytes classified as [`ByteClass::UmlautLead`] or [`ByteClass::CapitalSharpSLead`]
+/// can start an umlaut, all other bytes can be skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::bytes::{ByteClass, BYTE_CLASS};
+///
+/// let text = "Grüße, Straßenbahn".as_bytes();
+/// let candidates = text
+///     .iter()
+///     .filter(|&&byte| {
+///         matches!(
+///             BYTE_CLASS[usize::from(byte)],
+///             ByteClass::UmlautLead | ByteClass::CapitalSharpSLead
+///         )
+///     })
+///     .count();
+/// assert_eq!(candidates, 3);
+/// ```
+pub static BYTE_CLASS: [ByteClass; 256] = byte_classes();
+
+const fn byte_classes() -> [ByteClass; 256] {
+    let mut classes = [ByteClass::Invalid; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        classes[byte] = match byte {
+            0x00..=0x7f => ByteClass::Ascii,
+            0x80..=0xbf => ByteClass::Continuation,
+            0xc3 => ByteClass::UmlautLead,
+            0xc2..=0xdf => ByteClass::Lead2,
+            0xe1 => ByteClass::CapitalSharpSLead,
+            0xe0..=0xef => ByteClass::Lead3,
+            0xf0..=0xf4 => ByteClass::Lead4,
+            _ => ByteClass::Invalid,
+        };
+        byte += 1;
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fold::din_5007_2_at;
+
+    /// Returns the length of the valid UTF-8 sequences starting with `lead`.
+    fn sequence_len(lead: u8) -> Option<usize> {
+        (1..=4).find(|&len| {
+            let mut seq = [lead, 0x80, 0x80, 0x80];
+            // some lead bytes only allow a part of the continuation bytes as the second byte
+            (0x80..=0xbf).any(|second| {
+                seq[1] = second;
+                std::str::from_utf8(&seq[..len]).is_ok()
+            })
+        })
+    }
+
+    #[test]
+    fn byte_classes_match_utf8() {
+        for byte in 0..=255u8 {
+            let class = BYTE_CLASS[usize::from(byte)];
+            let expected = match sequence_len(byte) {
+                Some(1) => ByteClass::Ascii,
+                Some(2) if byte == 0xc3 => ByteClass::UmlautLead,
+                Some(2) => ByteClass::Lead2,
+                Some(3) if byte == 0xe1 => ByteClass::CapitalSharpSLead,
+                Some(3) => ByteClass::Lead3,
+                Some(4) => ByteClass::Lead4,
+                _ if byte & 0xc0 == 0x80 => ByteClass::Continuation,
+                _ => ByteClass::Invalid,
+            };
+            assert_eq!(class, expected, "byte {:#04x}", byte);
+        }
+    }
+
+    #[test]
+    fn only_umlaut_leads_start_umlauts() {
+        for byte in 0..=255u8 {
+            let starts_umlaut = (0x80..=0xbf).any(|second| {
+                (0x80..=0xbf).any(|third| din_5007_2_at(&[byte, second, third]).is_some())
+            });
+            let is_lead = matches!(
+                BYTE_CLASS[usize::from(byte)],
+                ByteClass::UmlautLead | ByteClass::CapitalSharpSLead
+            );
+            assert_eq!(starts_umlaut, is_lead, "byte {:#04x}", byte);
+        }
+    }
+
+    #[test]
+    fn encodings() {
+        let constants: [&[u8]; 8] = [
+            &A_DIAERESIS_LOWER,
+            &O_DIAERESIS_LOWER,
+            &U_DIAERESIS_LOWER,
+            &A_DIAERESIS_UPPER,
+            &O_DIAERESIS_UPPER,
+            &U_DIAERESIS_UPPER,
+            &SHARP_S,
+            &CAPITAL_SHARP_S,
+        ];
+        for (c, bytes) in "äöüÄÖÜßẞ".chars().zip(constants.iter()) {
+            assert_eq!(c.to_string().as_bytes(), *bytes);
+        }
+    }
+}
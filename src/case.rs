@@ -0,0 +1,200 @@
+//! Case conversions that may change the length of a text.
+use crate::utf8::decode_char;
+
+/// Uppercases the first alphabetic character.
+///
+/// See [`UmlautsOwned::capitalize_first_umlaut_aware`](crate::UmlautsOwned::capitalize_first_umlaut_aware).
+pub(crate) fn capitalize_first(bytes: &[u8]) -> Vec<u8> {
+    let mut capitalized = Vec::with_capacity(bytes.len() + 1);
+    let mut i = 0;
+    while i < bytes.len() {
+        let (c, len) = match decode_char(&bytes[i..]) {
+            Some((c, len)) if c.is_alphabetic() => (c, len),
+            Some((_, len)) => {
+                i += len;
+                continue;
+            }
+            None => {
+                i += 1;
+                continue;
+            }
+        };
+
+        capitalized.extend_from_slice(&bytes[..i]);
+        match c {
+            // title case of ß, the uppercase would be "SS"
+            'ß' => capitalized.extend_from_slice(b"Ss"),
+            c => {
+                let mut buf = [0; 4];
+                for upper in c.to_uppercase() {
+                    capitalized.extend_from_slice(upper.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
+        capitalized.extend_from_slice(&bytes[i + len..]);
+        return capitalized;
+    }
+
+    capitalized.extend_from_slice(bytes);
+    capitalized
+}
+
+/// Lowercases ASCII letters and the german uppercase letters 'Ä', 'Ö', 'Ü' and 'ẞ'.
+///
+/// See [`UmlautsOwned::to_german_lowercase`](crate::UmlautsOwned::to_german_lowercase).
+pub(crate) fn german_lowercase(bytes: &[u8]) -> Vec<u8> {
+    let mut lower = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        i += match bytes[i..] {
+            // Ä Ö Ü
+            [0xc3, second @ (0x84 | 0x96 | 0x9c), ..] => {
+                lower.extend_from_slice(&[0xc3, second + 0x20]);
+                2
+            }
+            // ẞ -> ß
+            [0xe1, 0xba, 0x9e, ..] => {
+                lower.extend_from_slice(&[0xc3, 0x9f]);
+                3
+            }
+            [byte, ..] => {
+                lower.push(byte.to_ascii_lowercase());
+                1
+            }
+            [] => unreachable!(),
+        };
+    }
+    lower
+}
+
+/// Splits `bytes` before every uppercase character following a lowercase character.
+///
+/// See [`UmlautsOwned::split_camel_umlaut_aware`](crate::UmlautsOwned::split_camel_umlaut_aware).
+pub(crate) fn split_camel(bytes: &[u8]) -> Vec<&[u8]> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut prev_lowercase = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let (c, len) = decode_char(&bytes[i..]).unwrap_or(('\u{fffd}', 1));
+        if prev_lowercase && c.is_uppercase() {
+            parts.push(&bytes[start..i]);
+            start = i;
+        }
+        prev_lowercase = c.is_lowercase();
+        i += len;
+    }
+    if start < bytes.len() {
+        parts.push(&bytes[start..]);
+    }
+    parts
+}
+
+/// Replaces every 'ß' and 'ẞ' with 'ẞ' if `to_capital` is set, with 'ß' otherwise.
+///
+/// See [`UmlautsOwned::unify_sharp_s`](crate::UmlautsOwned::unify_sharp_s).
+pub(crate) fn unify_sharp_s(bytes: &[u8], to_capital: bool) -> Vec<u8> {
+    let target = if to_capital { "ẞ" } else { "ß" };
+    let mut unified = Vec::with_capacity(bytes.len());
+    let mut last = 0;
+    let mut from = 0;
+    while let Some(offset) = memchr::memchr2(0xc3, 0xe1, &bytes[from..]) {
+        let i = from + offset;
+        let len = match bytes[i..] {
+            [0xc3, 0x9f, ..] => 2,
+            [0xe1, 0xba, 0x9e, ..] => 3,
+            _ => {
+                from = i + 1;
+                continue;
+            }
+        };
+        unified.extend_from_slice(&bytes[last..i]);
+        unified.extend_from_slice(target.as_bytes());
+        last = i + len;
+        from = last;
+    }
+    unified.extend_from_slice(&bytes[last..]);
+    unified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{capitalize_first, german_lowercase, split_camel, unify_sharp_s};
+
+    #[test]
+    fn unify_sharp_s_forms() {
+        let text = "Straße, STRAẞE, ßẞ".as_bytes();
+        assert_eq!(unify_sharp_s(text, false), "Straße, STRAßE, ßß".as_bytes());
+        assert_eq!(unify_sharp_s(text, true), "Straẞe, STRAẞE, ẞẞ".as_bytes());
+        assert_eq!(unify_sharp_s("Grüße".as_bytes(), true), "Grüẞe".as_bytes());
+        assert_eq!(
+            unify_sharp_s(&[b'a', 0xc3, 0xe1, 0xba], true),
+            [b'a', 0xc3, 0xe1, 0xba]
+        );
+        assert_eq!(unify_sharp_s(b"", false), b"");
+    }
+
+    fn split(text: &str) -> Vec<&str> {
+        split_camel(text.as_bytes())
+            .into_iter()
+            .map(|part| std::str::from_utf8(part).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn split_camel_case() {
+        assert_eq!(split("MüllerStraße"), vec!["Müller", "Straße"]);
+        assert_eq!(
+            split("HeizÖlRückstoßÄrger"),
+            vec!["Heiz", "Öl", "Rückstoß", "Ärger"]
+        );
+        assert_eq!(split("großẞ"), vec!["groß", "ẞ"]);
+        assert_eq!(split("GROẞE"), vec!["GROẞE"]);
+        assert_eq!(split("Kfz-Werkstatt"), vec!["Kfz-Werkstatt"]);
+        assert_eq!(split("ÖlÄra"), vec!["Öl", "Ära"]);
+        assert!(split("").is_empty());
+    }
+
+    #[test]
+    fn lowercase_german() {
+        assert_eq!(
+            german_lowercase("GRÜẞE ÖL".as_bytes()),
+            "grüße öl".as_bytes()
+        );
+        assert_eq!(
+            german_lowercase("ÄÖÜäöüßẞ".as_bytes()),
+            "äöüäöüßß".as_bytes()
+        );
+        assert_eq!(german_lowercase("CAFÉ".as_bytes()), "cafÉ".as_bytes());
+        assert_eq!(german_lowercase(&[b'A', 0xc3]), [b'a', 0xc3]);
+        assert_eq!(german_lowercase(&[0xe1, 0xba]), [0xe1, 0xba]);
+        assert_eq!(german_lowercase(b""), b"");
+    }
+
+    #[test]
+    fn capitalize_ascii() {
+        assert_eq!(capitalize_first(b"hallo welt"), b"Hallo welt");
+        assert_eq!(capitalize_first(b"Hallo"), b"Hallo");
+        assert_eq!(capitalize_first(b"\"hallo\""), b"\"Hallo\"");
+        assert_eq!(capitalize_first(b"42 tage"), b"42 Tage");
+    }
+
+    #[test]
+    fn capitalize_umlauts() {
+        assert_eq!(capitalize_first("ärger".as_bytes()), "Ärger".as_bytes());
+        assert_eq!(
+            capitalize_first("öl übermut".as_bytes()),
+            "Öl übermut".as_bytes()
+        );
+        assert_eq!(capitalize_first("„über“".as_bytes()), "„Über“".as_bytes());
+        assert_eq!(capitalize_first("ßa".as_bytes()), "Ssa".as_bytes());
+        assert_eq!(capitalize_first("ẞa".as_bytes()), "ẞa".as_bytes());
+    }
+
+    #[test]
+    fn capitalize_nothing() {
+        assert_eq!(capitalize_first(b""), b"");
+        assert_eq!(capitalize_first(b"123 !"), b"123 !");
+        assert_eq!(capitalize_first(&[0xc3, b' ', b'a']), [0xc3, b' ', b'A']);
+    }
+}
@@ -0,0 +1,124 @@
+//! Folding with user defined replacements.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::fold::{din_5007_2_at, next_umlaut};
+
+/// Error returned by [`CustomFolder::new`] for a key that is not an umlaut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownUmlautError {
+    bytes: [u8; 2],
+}
+
+impl UnknownUmlautError {
+    /// Returns the rejected key.
+    pub fn bytes(&self) -> [u8; 2] {
+        self.bytes
+    }
+}
+
+impl fmt::Display for UnknownUmlautError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bytes {:#04x} {:#04x} are not an umlaut",
+            self.bytes[0], self.bytes[1]
+        )
+    }
+}
+
+impl Error for UnknownUmlautError {}
+
+/// Folds umlauts with user defined replacements.
+///
+/// The mapping uses the two UTF-8 bytes of 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü' or 'ß' as keys.
+/// Umlauts without a mapping are left unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use std::collections::HashMap;
+/// use umlauts::CustomFolder;
+///
+/// let mut mapping = HashMap::new();
+/// mapping.insert([0xc3, 0xb6], b"oe".to_vec()); // ö
+/// mapping.insert([0xc3, 0x96], b"<OE>".to_vec()); // Ö
+/// let folder = CustomFolder::new(mapping).unwrap();
+/// assert_eq!(folder.fold("Öl und Möhren".as_bytes()), b"<OE>l und Moehren");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CustomFolder {
+    mapping: HashMap<[u8; 2], Vec<u8>>,
+}
+
+impl CustomFolder {
+    /// Creates a folder from a mapping of umlaut byte pairs to their replacements.
+    ///
+    /// Fails if a key is not one of the umlauts 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü' or 'ß'.
+    pub fn new(mapping: HashMap<[u8; 2], Vec<u8>>) -> Result<CustomFolder, UnknownUmlautError> {
+        if let Some(&bytes) = mapping
+            .keys()
+            .find(|key| !matches!(din_5007_2_at(&key[..]), Some((2, _))))
+        {
+            return Err(UnknownUmlautError { bytes });
+        }
+        Ok(CustomFolder { mapping })
+    }
+
+    /// Replaces all mapped umlauts in `bytes`.
+    pub fn fold(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(bytes.len());
+        let mut last = 0;
+        while let Some((range, _)) = next_umlaut(bytes, last) {
+            folded.extend_from_slice(&bytes[last..range.start]);
+            let umlaut = &bytes[range.clone()];
+            match umlaut {
+                &[first, second] => match self.mapping.get(&[first, second]) {
+                    Some(replacement) => folded.extend_from_slice(replacement),
+                    None => folded.extend_from_slice(umlaut),
+                },
+                _ => folded.extend_from_slice(umlaut),
+            }
+            last = range.end;
+        }
+        folded.extend_from_slice(&bytes[last..]);
+        folded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::CustomFolder;
+
+    #[test]
+    fn fold_custom() {
+        let mut mapping = HashMap::new();
+        mapping.insert([0xc3, 0xa4], b"a".to_vec());
+        mapping.insert([0xc3, 0x9f], b"sz".to_vec());
+        let folder = CustomFolder::new(mapping).unwrap();
+        assert_eq!(
+            folder.fold("Bär Maß Öl".as_bytes()),
+            "Bar Masz Öl".as_bytes()
+        );
+        assert_eq!(folder.fold("GROẞ".as_bytes()), "GROẞ".as_bytes());
+        assert_eq!(folder.fold(b""), b"");
+    }
+
+    #[test]
+    fn reject_unknown_keys() {
+        let mut mapping = HashMap::new();
+        mapping.insert([0xc3, 0xa4], b"ae".to_vec());
+        mapping.insert([0xc3, 0xa9], b"e".to_vec()); // é
+        let err = CustomFolder::new(mapping).unwrap_err();
+        assert_eq!(err.bytes(), [0xc3, 0xa9]);
+        assert_eq!(err.to_string(), "bytes 0xc3 0xa9 are not an umlaut");
+
+        let mut mapping = HashMap::new();
+        mapping.insert([b'a', b'e'], "ä".as_bytes().to_vec());
+        assert!(CustomFolder::new(mapping).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! Folding selected columns of delimited text like CSV.
+use crate::Transliterator;
+
+/// Settings for [`fold_delimited`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{fold_delimited, CasePolicy, DelimitedStyle, Transliterator};
+///
+/// let style = DelimitedStyle::new()
+///     .transliterator(Transliterator::new().case(CasePolicy::Upper))
+///     .skip_quoted(true);
+/// let line = "Müller;\"Grüße\";Köln".as_bytes();
+/// assert_eq!(
+///     fold_delimited(line, b';', &[0, 1, 2], &style),
+///     "MUELLER;\"Grüße\";KOELN".as_bytes()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DelimitedStyle {
+    transliterator: Transliterator,
+    skip_quoted: bool,
+}
+
+impl DelimitedStyle {
+    /// Creates a style folding all selected fields with the default [`Transliterator`].
+    pub fn new() -> DelimitedStyle {
+        DelimitedStyle::default()
+    }
+
+    /// Sets the transliterator used to fold the fields.
+    pub fn transliterator(mut self, transliterator: Transliterator) -> DelimitedStyle {
+        self.transliterator = transliterator;
+        self
+    }
+
+    /// Sets if fields enclosed in double quotes are left unchanged, defaults to `false`.
+    pub fn skip_quoted(mut self, skip: bool) -> DelimitedStyle {
+        self.skip_quoted = skip;
+        self
+    }
+}
+
+/// Converts the umlauts of a single CSV field to ae, oe, ue, ss, ...
+///
+/// Maps umlauts according to DIN 5007-2 with the default [`Transliterator`].
+/// Quotes are ASCII and kept, so quoted fields stay quoted.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::fold_csv_field;
+///
+/// assert_eq!(fold_csv_field("\"Müller, Jürgen\"".as_bytes()), b"\"Mueller, Juergen\"");
+/// ```
+pub fn fold_csv_field(field: &[u8]) -> Vec<u8> {
+    Transliterator::new().fold_ascii(field)
+}
+
+/// Folds the fields at the zero based indices `columns` of a delimited line.
+///
+/// The line is split on `delimiter`, except inside fields enclosed in double quotes,
+/// so a quoted field may contain the delimiter. The selected fields are folded
+/// according to `style`, all other fields and the delimiters are copied unchanged.
+/// Indices without a matching field are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{fold_delimited, DelimitedStyle};
+///
+/// let line = "42,\"Müller, Jürgen\",Köln".as_bytes();
+/// assert_eq!(
+///     fold_delimited(line, b',', &[1], &DelimitedStyle::new()),
+///     "42,\"Mueller, Juergen\",Köln".as_bytes()
+/// );
+/// ```
+pub fn fold_delimited(
+    line: &[u8],
+    delimiter: u8,
+    columns: &[usize],
+    style: &DelimitedStyle,
+) -> Vec<u8> {
+    let mut folded = Vec::with_capacity(line.len());
+    for (column, field) in split_fields(line, delimiter).enumerate() {
+        if column > 0 {
+            folded.push(delimiter);
+        }
+        let quoted = field.first() == Some(&b'"');
+        if columns.contains(&column) && !(quoted && style.skip_quoted) {
+            folded.extend_from_slice(&style.transliterator.fold_ascii(field));
+        } else {
+            folded.extend_from_slice(field);
+        }
+    }
+    folded
+}
+
+/// Splits `line` on `delimiter` outside of double quotes.
+fn split_fields(line: &[u8], delimiter: u8) -> impl Iterator<Item = &[u8]> {
+    let mut in_quotes = false;
+    line.split(move |&byte| {
+        if byte == b'"' {
+            in_quotes = !in_quotes;
+        }
+        byte == delimiter && !in_quotes
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fold_delimited, split_fields, DelimitedStyle};
+    use crate::{CasePolicy, Transliterator};
+
+    fn fold(line: &str, columns: &[usize], style: &DelimitedStyle) -> String {
+        String::from_utf8(fold_delimited(line.as_bytes(), b';', columns, style)).unwrap()
+    }
+
+    #[test]
+    fn split_quoted() {
+        let fields: Vec<_> = split_fields(b"a;\"b;c\";\"d\"\"e;\";", b';').collect();
+        assert_eq!(fields, vec![&b"a"[..], b"\"b;c\"", b"\"d\"\"e;\"", b""]);
+        assert_eq!(split_fields(b"", b';').count(), 1);
+    }
+
+    #[test]
+    fn selected_columns() {
+        let style = DelimitedStyle::new();
+        let line = "Jürgen;Müller;Köln;Straße";
+        assert_eq!(fold(line, &[1, 3], &style), "Jürgen;Mueller;Köln;Strasse");
+        assert_eq!(fold(line, &[], &style), line);
+        assert_eq!(fold(line, &[7], &style), line);
+        assert_eq!(fold(";;Ä;", &[2, 3], &style), ";;Ae;");
+        assert_eq!(fold("", &[0], &style), "");
+    }
+
+    #[test]
+    fn quoted_fields() {
+        let line = "\"Müller; Jürgen\";Köln";
+        assert_eq!(
+            fold(line, &[0, 1], &DelimitedStyle::new()),
+            "\"Mueller; Juergen\";Koeln"
+        );
+        let style = DelimitedStyle::new()
+            .skip_quoted(true)
+            .transliterator(Transliterator::new().case(CasePolicy::Lower));
+        assert_eq!(fold(line, &[0, 1], &style), "\"Müller; Jürgen\";koeln");
+    }
+}
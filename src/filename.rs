@@ -0,0 +1,103 @@
+//! Folding file names to a form that is safe on common file systems.
+use crate::fold::fold_din_5007_2;
+
+/// Characters that are reserved in file names on Windows and FAT file systems.
+const RESERVED: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Settings for folding file names.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::FilenameStyle;
+///
+/// let style = FilenameStyle::new().reserved(vec!['/', ' ']).replacement('-');
+/// assert_eq!(style.to_safe_filename("Größe/Übung 2.txt".as_bytes()), "Groesse-Uebung-2.txt");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameStyle {
+    reserved: Vec<char>,
+    replacement: char,
+}
+
+impl Default for FilenameStyle {
+    fn default() -> FilenameStyle {
+        FilenameStyle {
+            reserved: RESERVED.to_vec(),
+            replacement: '_',
+        }
+    }
+}
+
+impl FilenameStyle {
+    /// Creates a style replacing the characters `<>:"/\|?*` with '_'.
+    pub fn new() -> FilenameStyle {
+        FilenameStyle::default()
+    }
+
+    /// Sets the characters that are replaced.
+    pub fn reserved(mut self, reserved: Vec<char>) -> FilenameStyle {
+        self.reserved = reserved;
+        self
+    }
+
+    /// Sets the character the reserved characters are replaced with, defaults to '_'.
+    pub fn replacement(mut self, replacement: char) -> FilenameStyle {
+        self.replacement = replacement;
+        self
+    }
+
+    /// Folds `name` according to DIN 5007-2 and replaces the reserved characters.
+    ///
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    pub fn to_safe_filename(&self, name: &[u8]) -> String {
+        let mut folded = Vec::with_capacity(name.len());
+        fold_din_5007_2(name, &mut folded);
+        String::from_utf8_lossy(&folded)
+            .chars()
+            .map(|c| {
+                if self.reserved.contains(&c) {
+                    self.replacement
+                } else {
+                    c
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FilenameStyle;
+
+    #[test]
+    fn default_style() {
+        let style = FilenameStyle::new();
+        assert_eq!(
+            style.to_safe_filename("Größe/Übung?.txt".as_bytes()),
+            "Groesse_Uebung_.txt"
+        );
+        assert_eq!(
+            style.to_safe_filename(r#"a<b>c:d"e\f|g*h"#.as_bytes()),
+            "a_b_c_d_e_f_g_h"
+        );
+        assert_eq!(style.to_safe_filename(b""), "");
+        assert_eq!(style.to_safe_filename(&[b'a', 0xff]), "a\u{fffd}");
+    }
+
+    #[test]
+    fn custom_style() {
+        let style = FilenameStyle::new()
+            .reserved(vec!['ä', '.'])
+            .replacement('é');
+        // umlauts are folded before the reserved characters are replaced
+        assert_eq!(style.to_safe_filename("Bär.txt".as_bytes()), "Baerétxt");
+        assert_eq!(
+            FilenameStyle::new()
+                .reserved(Vec::new())
+                .to_safe_filename("a/b".as_bytes()),
+            "a/b"
+        );
+    }
+}
@@ -0,0 +1,873 @@
+//! Scanning and DIN 5007-2 mapping of umlauts.
+use crate::transliterator::{in_uppercase_word, is_uppercase_context};
+use crate::utf8::decode_char;
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+/// Returns the byte length and the DIN 5007-2 replacement of the umlaut at the start of `bytes`.
+pub(crate) fn din_5007_2_at(bytes: &[u8]) -> Option<(usize, &'static [u8])> {
+    match bytes {
+        [0xc3, 0xa4, ..] => Some((2, b"ae")),
+        [0xc3, 0xb6, ..] => Some((2, b"oe")),
+        [0xc3, 0xbc, ..] => Some((2, b"ue")),
+        [0xc3, 0x84, ..] => Some((2, b"Ae")),
+        [0xc3, 0x96, ..] => Some((2, b"Oe")),
+        [0xc3, 0x9c, ..] => Some((2, b"Ue")),
+        [0xc3, 0x9f, ..] => Some((2, b"ss")),
+        // ẞ
+        [0xe1, 0xba, 0x9e, ..] => Some((3, b"SS")),
+        _ => None,
+    }
+}
+
+/// Result of folding a single character with [`fold_char_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoldResult {
+    /// The character is not an umlaut and is kept as it is.
+    Unchanged,
+    /// The character is an umlaut with this DIN 5007-2 replacement.
+    Replaced([u8; 2]),
+}
+
+impl FoldResult {
+    /// Returns the replacement, or `None` if the character is kept.
+    pub fn replacement(&self) -> Option<&[u8]> {
+        match self {
+            FoldResult::Unchanged => None,
+            FoldResult::Replaced(bytes) => Some(bytes),
+        }
+    }
+}
+
+/// Folds the character at the start of `buf` according to DIN 5007-2.
+///
+/// Returns how the character is folded and the number of bytes it consumed:
+/// 1 for ASCII, 2 for 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü' and 'ß', 3 for 'ẞ' and the length of
+/// the UTF-8 sequence for other characters. Invalid bytes are consumed one at a time.
+/// For an empty `buf`, `(FoldResult::Unchanged, 0)` is returned.
+///
+/// `buf` has to start at a character boundary.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{fold_char_bytes, FoldResult};
+///
+/// let text = "aß€".as_bytes();
+/// assert_eq!(fold_char_bytes(text), (FoldResult::Unchanged, 1));
+/// assert_eq!(fold_char_bytes(&text[1..]), (FoldResult::Replaced(*b"ss"), 2));
+/// assert_eq!(fold_char_bytes(&text[3..]), (FoldResult::Unchanged, 3));
+///
+/// let mut folded = Vec::new();
+/// let mut rest = "Grüße".as_bytes();
+/// while !rest.is_empty() {
+///     let (result, len) = fold_char_bytes(rest);
+///     folded.extend_from_slice(result.replacement().unwrap_or(&rest[..len]));
+///     rest = &rest[len..];
+/// }
+/// assert_eq!(folded, b"Gruesse");
+/// ```
+pub fn fold_char_bytes(buf: &[u8]) -> (FoldResult, usize) {
+    if let Some((len, &[first, second])) = din_5007_2_at(buf) {
+        return (FoldResult::Replaced([first, second]), len);
+    }
+    let len = decode_char(buf).map_or(buf.len().min(1), |(_, len)| len);
+    (FoldResult::Unchanged, len)
+}
+
+/// Finds the next umlaut at or after `from`.
+///
+/// Returns the byte range of the umlaut and its DIN 5007-2 replacement.
+pub(crate) fn next_umlaut(bytes: &[u8], mut from: usize) -> Option<(Range<usize>, &'static [u8])> {
+    while from < bytes.len() {
+        let i = from + memchr::memchr2(0xc3, 0xe1, &bytes[from..])?;
+        if let Some((len, replacement)) = din_5007_2_at(&bytes[i..]) {
+            return Some((i..i + len, replacement));
+        }
+        from = i + 1;
+    }
+    None
+}
+
+/// Chooses the case of the expansion of an 'ß' from its neighbouring bytes.
+///
+/// `prev` and `next` are the bytes directly before and after the 'ß', if any.
+/// Only ASCII letters are considered uppercase or lowercase.
+/// This is the rule [`Transliterator::fold_ascii`](crate::Transliterator::fold_ascii)
+/// uses with [`SharpSCase::FollowContext`](crate::SharpSCase::FollowContext), the next
+/// letter decides and the previous one only if no letter follows. The expansion is
+/// - `"SS"` if `next` is uppercase, like in "GROßE" or "oßE",
+/// - `"SS"` if `next` is missing or not a letter and `prev` is uppercase, like in "GROß",
+/// - `"ss"` otherwise, like in "Straße" or "Sße".
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::sharp_s_expansion;
+///
+/// assert_eq!(sharp_s_expansion(Some(b'O'), Some(b'E')), b"SS");
+/// assert_eq!(sharp_s_expansion(Some(b'O'), None), b"SS");
+/// assert_eq!(sharp_s_expansion(Some(b'O'), Some(b'e')), b"ss");
+/// assert_eq!(sharp_s_expansion(Some(b'o'), Some(b'e')), b"ss");
+/// ```
+pub fn sharp_s_expansion(prev: Option<u8>, next: Option<u8>) -> &'static [u8] {
+    let ascii = |byte: Option<u8>| byte.filter(u8::is_ascii).map(char::from);
+    if is_uppercase_context(ascii(prev), ascii(next)) {
+        b"SS"
+    } else {
+        b"ss"
+    }
+}
+
+/// Finds the next umlaut at or after `from`.
+///
+/// Returns the index of the first byte of the next 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü', 'ß' or 'ẞ'.
+/// Only complete UTF-8 sequences of these characters are found,
+/// other characters sharing the same lead byte like 'é' are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::find_next_umlaut;
+///
+/// let text = "café Müller".as_bytes();
+/// assert_eq!(find_next_umlaut(text, 0), Some(7));
+/// assert_eq!(find_next_umlaut(text, 8), None);
+/// ```
+pub fn find_next_umlaut(bytes: &[u8], from: usize) -> Option<usize> {
+    next_umlaut(bytes, from).map(|(range, _)| range.start)
+}
+
+/// Appends `bytes` to `out` with all umlauts replaced according to DIN 5007-2.
+///
+/// Returns if any umlaut has been replaced.
+pub(crate) fn fold_din_5007_2(bytes: &[u8], out: &mut Vec<u8>) -> bool {
+    let mut last = 0;
+    while let Some((range, replacement)) = next_umlaut(bytes, last) {
+        out.extend_from_slice(&bytes[last..range.start]);
+        out.extend_from_slice(replacement);
+        last = range.end;
+    }
+    out.extend_from_slice(&bytes[last..]);
+    last != 0
+}
+
+/// Finds the next umlaut at or after `from` that the in place fold writes differently.
+///
+/// These are 'ẞ', which can't be expanded in place, and 'ß' in an uppercase word,
+/// which [`Transliterator::fold_ascii`](crate::Transliterator::fold_ascii) expands to "SS".
+/// See [`UmlautsOwned::unsafe_inplace_positions`](crate::UmlautsOwned::unsafe_inplace_positions).
+pub(crate) fn next_lossy_in_place(bytes: &[u8], mut from: usize) -> Option<usize> {
+    while let Some((range, replacement)) = next_umlaut(bytes, from) {
+        let lossy = match bytes[range.start..] {
+            [0xe1, ..] => true,
+            _ => replacement == b"ss" && in_uppercase_word(bytes, range.start, range.end),
+        };
+        if lossy {
+            return Some(range.start);
+        }
+        from = range.end;
+    }
+    None
+}
+
+/// Returns the byte length and the replacement of a character at `bytes[i..]` accepted by
+/// [`fold_strict`].
+fn strict_replacement_at(bytes: &[u8], i: usize) -> Option<(usize, &'static [u8])> {
+    let umlaut = din_5007_2_at(&bytes[i..]);
+    #[cfg(feature = "latin")]
+    let umlaut = umlaut.or_else(|| crate::latin::latin_at(bytes, i));
+    #[cfg(feature = "symbols")]
+    let umlaut = umlaut.or_else(|| crate::symbols::symbol_at(&bytes[i..]));
+    umlaut
+}
+
+/// Folds `bytes` according to DIN 5007-2, rejecting all other non-ASCII characters.
+///
+/// See [`UmlautsOwned::fold_ascii_strict`](crate::UmlautsOwned::fold_ascii_strict).
+pub(crate) fn fold_strict(bytes: &[u8]) -> Result<Vec<u8>, Vec<(usize, char)>> {
+    let mut folded = Vec::with_capacity(bytes.len());
+    let mut rejected = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii() {
+            folded.push(bytes[i]);
+            i += 1;
+        } else if let Some((len, replacement)) = strict_replacement_at(bytes, i) {
+            folded.extend_from_slice(replacement);
+            i += len;
+        } else {
+            let (c, len) = decode_char(&bytes[i..]).unwrap_or((char::REPLACEMENT_CHARACTER, 1));
+            #[cfg(feature = "log")]
+            log::warn!("can't fold {:?} at offset {} to ASCII", c, i);
+            rejected.push((i, c));
+            i += len;
+        }
+    }
+    if rejected.is_empty() {
+        Ok(folded)
+    } else {
+        Err(rejected)
+    }
+}
+
+/// Error returned by [`UmlautsOwned::ensure_folded_ascii`](crate::UmlautsOwned::ensure_folded_ascii)
+/// for a text containing a non-ASCII byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonAsciiError {
+    position: usize,
+    byte: u8,
+}
+
+impl NonAsciiError {
+    /// Returns the offset of the first non-ASCII byte.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the first non-ASCII byte.
+    pub fn byte(&self) -> u8 {
+        self.byte
+    }
+}
+
+impl fmt::Display for NonAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "non-ASCII byte {:#04x} at offset {}",
+            self.byte, self.position
+        )
+    }
+}
+
+impl Error for NonAsciiError {}
+
+/// Returns an error for the first non-ASCII byte in `bytes`.
+pub(crate) fn ensure_ascii(bytes: &[u8]) -> Result<(), NonAsciiError> {
+    match bytes.iter().position(|byte| !byte.is_ascii()) {
+        Some(position) => Err(NonAsciiError {
+            position,
+            byte: bytes[position],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks if `a` and `b` look like the DIN 5007-2 replacement of an umlaut, ignoring case.
+fn is_replacement_pair(a: u8, b: u8) -> bool {
+    matches!(
+        (a.to_ascii_lowercase(), b.to_ascii_lowercase()),
+        (b'a', b'e') | (b'o', b'e') | (b'u', b'e') | (b's', b's')
+    )
+}
+
+/// Checks if the DIN 5007-2 folded form of `bytes` can be reversed unambiguously.
+///
+/// See [`UmlautsOwned::is_ascii_fold_reversible`](crate::UmlautsOwned::is_ascii_fold_reversible).
+pub(crate) fn is_fold_reversible(bytes: &[u8]) -> bool {
+    let mut folded = Vec::with_capacity(bytes.len());
+    let mut from_umlaut = Vec::with_capacity(bytes.len());
+    let mut last = 0;
+    while let Some((range, replacement)) = next_umlaut(bytes, last) {
+        folded.extend_from_slice(&bytes[last..range.start]);
+        folded.extend_from_slice(replacement);
+        from_umlaut.resize(folded.len() - 2, false);
+        from_umlaut.extend_from_slice(&[true, false]);
+        last = range.end;
+    }
+    folded.extend_from_slice(&bytes[last..]);
+    from_umlaut.resize(folded.len(), false);
+
+    // simulate reading the folded form from left to right
+    let mut i = 0;
+    while i < folded.len() {
+        if i + 1 < folded.len() && is_replacement_pair(folded[i], folded[i + 1]) {
+            if !from_umlaut[i] {
+                return false;
+            }
+            i += 2;
+        } else {
+            i += 1;
+        }
+    }
+    true
+}
+
+/// Like [`fold_din_5007_2`], but appends at most `max` bytes.
+///
+/// Stops before a replacement or a multi-byte character that does not fit completely.
+pub(crate) fn fold_din_5007_2_capped(bytes: &[u8], max: usize, out: &mut Vec<u8>) {
+    let limit = out.len() + max;
+    let mut last = 0;
+    loop {
+        let next = next_umlaut(bytes, last);
+        let plain = &bytes[last..next.as_ref().map_or(bytes.len(), |(range, _)| range.start)];
+        let remaining = limit - out.len();
+        if plain.len() > remaining {
+            let mut cut = remaining;
+            while cut > 0 && plain[cut] & 0xc0 == 0x80 {
+                cut -= 1;
+            }
+            out.extend_from_slice(&plain[..cut]);
+            return;
+        }
+        out.extend_from_slice(plain);
+        match next {
+            Some((range, replacement)) if replacement.len() <= limit - out.len() => {
+                out.extend_from_slice(replacement);
+                last = range.end;
+            }
+            _ => return,
+        }
+    }
+}
+
+/// Iterator over the bytes of a text folded according to DIN 5007-2.
+#[derive(Debug, Clone)]
+pub(crate) struct FoldedBytes<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    replacement: &'static [u8],
+}
+
+impl<'a> FoldedBytes<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> FoldedBytes<'a> {
+        FoldedBytes {
+            bytes,
+            pos: 0,
+            replacement: &[],
+        }
+    }
+}
+
+impl<'a> Iterator for FoldedBytes<'a> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some((&byte, rest)) = self.replacement.split_first() {
+            self.replacement = rest;
+            return Some(byte);
+        }
+        let rest = self.bytes.get(self.pos..).filter(|rest| !rest.is_empty())?;
+        match din_5007_2_at(rest) {
+            Some((len, replacement)) => {
+                self.pos += len;
+                self.replacement = &replacement[1..];
+                Some(replacement[0])
+            }
+            None => {
+                self.pos += 1;
+                Some(rest[0])
+            }
+        }
+    }
+}
+
+/// Searches for `pattern` in `line`, ignoring umlaut spelling and ASCII case.
+///
+/// Both are folded according to DIN 5007-2 and lowercased before comparing,
+/// so "gruesse" matches "Grüße" and "GRÜSSE".
+/// Only the pattern is folded into a new buffer, the line is folded while searching.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::matches_folded;
+///
+/// assert!(matches_folded("Viele Grüße".as_bytes(), b"gruesse"));
+/// assert!(matches_folded(b"Viele Gruesse", "Grüße".as_bytes()));
+/// assert!(!matches_folded("Viele Grüße".as_bytes(), b"grusse"));
+/// ```
+pub fn matches_folded(line: &[u8], pattern: &[u8]) -> bool {
+    let mut folded_pattern = Vec::with_capacity(pattern.len());
+    fold_din_5007_2(pattern, &mut folded_pattern);
+    folded_pattern.make_ascii_lowercase();
+
+    let mut start = FoldedBytes::new(line);
+    loop {
+        let mut candidate = start.clone();
+        if folded_pattern.iter().all(|&expected| {
+            candidate.next().map(|byte| byte.to_ascii_lowercase()) == Some(expected)
+        }) {
+            return true;
+        }
+        if start.next().is_none() {
+            return false;
+        }
+    }
+}
+
+/// Checks if `a` and `b` are the same word with a different umlaut spelling.
+///
+/// Both are folded according to DIN 5007-2 and compared ignoring ASCII case,
+/// so "Müller", "Mueller", "MÜLLER" and "müller" are all variants of each other,
+/// as are "Straße" and "Strasse". "Muller" is a different name though.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::are_spelling_variants;
+///
+/// assert!(are_spelling_variants("Müller".as_bytes(), b"Mueller"));
+/// assert!(are_spelling_variants("Straße".as_bytes(), b"STRASSE"));
+/// assert!(!are_spelling_variants("Müller".as_bytes(), b"Muller"));
+/// ```
+pub fn are_spelling_variants(a: &[u8], b: &[u8]) -> bool {
+    FoldedBytes::new(a)
+        .map(|byte| byte.to_ascii_lowercase())
+        .eq(FoldedBytes::new(b).map(|byte| byte.to_ascii_lowercase()))
+}
+
+/// Iterator over the umlauts of a text and their DIN 5007-2 replacements.
+///
+/// Created by [`UmlautsOwned::fold_diff`](crate::UmlautsOwned::fold_diff).
+#[derive(Debug, Clone)]
+pub struct FoldDiff<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FoldDiff<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> FoldDiff<'a> {
+        FoldDiff { bytes, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for FoldDiff<'a> {
+    type Item = (Range<usize>, &'static [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match next_umlaut(self.bytes, self.pos) {
+            Some((range, replacement)) => {
+                self.pos = range.end;
+                Some((range, replacement))
+            }
+            None => {
+                self.pos = self.bytes.len();
+                None
+            }
+        }
+    }
+}
+
+/// Iterator over the characters of a text and their DIN 5007-2 replacements.
+///
+/// Yields the byte offset of each character in the original text, the character
+/// and its folded bytes, which are the original bytes for characters other than umlauts.
+/// Every byte that does not start a valid UTF-8 sequence is yielded on its own as
+/// U+FFFD with the byte as folded bytes.
+///
+/// Created by [`UmlautsOwned::fold_trace`](crate::UmlautsOwned::fold_trace).
+#[derive(Debug, Clone)]
+pub struct FoldTrace<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FoldTrace<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> FoldTrace<'a> {
+        FoldTrace { bytes, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for FoldTrace<'a> {
+    type Item = (usize, char, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+        let rest = self.bytes.get(start..).filter(|rest| !rest.is_empty())?;
+        let (c, len) = decode_char(rest).unwrap_or(('\u{fffd}', 1));
+        self.pos += len;
+        let folded = match din_5007_2_at(rest) {
+            Some((_, replacement)) => replacement,
+            None => &rest[..len],
+        };
+        Some((start, c, folded))
+    }
+}
+
+/// Iterator over the whitespace separated tokens of a text, folded according to DIN 5007-2.
+///
+/// Yields the byte range of each token in the original text and its folded form.
+///
+/// Created by [`UmlautsOwned::folded_tokens`](crate::UmlautsOwned::folded_tokens).
+#[derive(Debug, Clone)]
+pub struct FoldedTokens<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> FoldedTokens<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> FoldedTokens<'a> {
+        FoldedTokens { bytes, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for FoldedTokens<'a> {
+    type Item = (Range<usize>, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.bytes[self.pos..];
+        let start = self.pos + rest.iter().position(|byte| !byte.is_ascii_whitespace())?;
+        let end = self.bytes[start..]
+            .iter()
+            .position(|byte| byte.is_ascii_whitespace())
+            .map_or(self.bytes.len(), |len| start + len);
+        self.pos = end;
+        let mut folded = Vec::with_capacity(end - start);
+        fold_din_5007_2(&self.bytes[start..end], &mut folded);
+        Some((start..end, folded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        are_spelling_variants, find_next_umlaut, fold_char_bytes, fold_din_5007_2,
+        fold_din_5007_2_capped, fold_strict, is_fold_reversible, matches_folded, sharp_s_expansion,
+        FoldDiff, FoldResult, FoldTrace, FoldedBytes, FoldedTokens,
+    };
+    use crate::{Transliterator, UmlautsOwned};
+
+    #[test]
+    fn strict() {
+        assert_eq!(
+            fold_strict("Grüße aus Köln".as_bytes()),
+            Ok(b"Gruesse aus Koeln".to_vec())
+        );
+        assert_eq!(
+            fold_strict("Café in Zürich 🦀".as_bytes()),
+            Err(vec![(3, 'é'), (17, '🦀')])
+        );
+        assert_eq!(
+            fold_strict(&[b'a', 0xc3, b'b', 0xe1, 0xba]),
+            Err(vec![(1, '\u{fffd}'), (3, '\u{fffd}'), (4, '\u{fffd}')])
+        );
+        assert_eq!(fold_strict(b""), Ok(Vec::new()));
+        let folded = fold_strict("ÄÖÜäöüßẞ".as_bytes()).unwrap();
+        assert_eq!(folded.ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn spelling_variants() {
+        let cases: &[(&str, &str, bool)] = &[
+            ("Müller", "Mueller", true),
+            ("Müller", "müller", true),
+            ("Müller", "MÜLLER", true),
+            ("MUELLER", "müller", true),
+            ("Straße", "Strasse", true),
+            ("STRAẞE", "straße", true),
+            ("Grüße", "GRUESSE", true),
+            ("", "", true),
+            ("Müller", "Muller", false),
+            ("Müller", "Möller", false),
+            ("Straße", "Strase", false),
+            ("Müller", "Müllers", false),
+            ("Café", "CAFÉ", false),
+            ("Mü", "", false),
+        ];
+        for &(a, b, expected) in cases {
+            assert_eq!(
+                are_spelling_variants(a.as_bytes(), b.as_bytes()),
+                expected,
+                "{} {}",
+                a,
+                b
+            );
+            assert_eq!(are_spelling_variants(b.as_bytes(), a.as_bytes()), expected);
+        }
+    }
+
+    #[test]
+    fn folded_tokens() {
+        let text = "  Grüße aus\tder Königstraße\n";
+        let tokens: Vec<_> = FoldedTokens::new(text.as_bytes()).collect();
+        assert_eq!(
+            tokens,
+            vec![
+                (2..9, b"Gruesse".to_vec()),
+                (10..13, b"aus".to_vec()),
+                (14..17, b"der".to_vec()),
+                (18..31, b"Koenigstrasse".to_vec()),
+            ]
+        );
+        assert_eq!(&text[18..31], "Königstraße");
+        for (_, token) in FoldedTokens::new("ÄÖÜ äöü ßẞ".as_bytes()) {
+            assert_eq!(token.ensure_folded_ascii(), Ok(()));
+        }
+        assert_eq!(FoldedTokens::new(b" \t ").next(), None);
+        assert_eq!(FoldedTokens::new(b"").next(), None);
+    }
+
+    #[test]
+    fn char_bytes() {
+        let cases: &[(&[u8], FoldResult, usize)] = &[
+            (b"a", FoldResult::Unchanged, 1),
+            ("ä".as_bytes(), FoldResult::Replaced(*b"ae"), 2),
+            ("Üb".as_bytes(), FoldResult::Replaced(*b"Ue"), 2),
+            ("ß".as_bytes(), FoldResult::Replaced(*b"ss"), 2),
+            ("ẞ".as_bytes(), FoldResult::Replaced(*b"SS"), 3),
+            ("é".as_bytes(), FoldResult::Unchanged, 2),
+            ("🦀".as_bytes(), FoldResult::Unchanged, 4),
+            (&[0xc3], FoldResult::Unchanged, 1),
+            (&[0xe1, 0xba], FoldResult::Unchanged, 1),
+            (&[0xa4, b'a'], FoldResult::Unchanged, 1),
+            (b"", FoldResult::Unchanged, 0),
+        ];
+        for &(buf, result, len) in cases {
+            assert_eq!(fold_char_bytes(buf), (result, len), "{:?}", buf);
+        }
+        assert_eq!(FoldResult::Replaced(*b"oe").replacement(), Some(&b"oe"[..]));
+        assert_eq!(FoldResult::Unchanged.replacement(), None);
+    }
+
+    #[test]
+    fn sharp_s_expansions() {
+        let cases: &[(Option<u8>, Option<u8>, &[u8])] = &[
+            (Some(b'O'), Some(b'E'), b"SS"),
+            (Some(b'O'), None, b"SS"),
+            (Some(b'O'), Some(b' '), b"SS"),
+            (None, Some(b'E'), b"SS"),
+            (Some(b'-'), Some(b'E'), b"SS"),
+            (Some(b'O'), Some(b'e'), b"ss"),
+            (Some(b'o'), Some(b'e'), b"ss"),
+            (Some(b'o'), Some(b'E'), b"SS"),
+            (Some(b'a'), None, b"ss"),
+            (None, Some(b'e'), b"ss"),
+            (None, None, b"ss"),
+            (Some(0x9c), Some(b'E'), b"SS"),
+        ];
+        for &(prev, next, expansion) in cases {
+            assert_eq!(
+                sharp_s_expansion(prev, next),
+                expansion,
+                "{:?} {:?}",
+                prev,
+                next
+            );
+            // the same expansion as the default transliterator
+            let text = [prev.as_slice(), "ß".as_bytes(), next.as_slice()].concat();
+            let folded = Transliterator::new().fold_ascii(&text);
+            let start = prev.map_or(0, |_| 1);
+            assert_eq!(
+                &folded[start..start + 2],
+                expansion,
+                "{:?} {:?}",
+                prev,
+                next
+            );
+        }
+    }
+
+    #[test]
+    fn folded_bytes() {
+        for text in ["Öl Ärmel Übermut", "GROẞE Straße", "café", "", "\u{c3}"].iter() {
+            let mut folded = Vec::new();
+            fold_din_5007_2(text.as_bytes(), &mut folded);
+            assert_eq!(
+                FoldedBytes::new(text.as_bytes()).collect::<Vec<_>>(),
+                folded
+            );
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let line = "Viele Grüße aus Köln".as_bytes();
+        assert!(matches_folded(line, b"gruesse"));
+        assert!(matches_folded(line, b"GRUESSE"));
+        assert!(matches_folded(line, "grüße".as_bytes()));
+        assert!(matches_folded(line, b"uess"));
+        assert!(matches_folded(line, b"ess"));
+        assert!(matches_folded(line, b"koeln"));
+        assert!(matches_folded(line, b""));
+        assert!(!matches_folded(line, b"grusse"));
+        assert!(!matches_folded(line, b"koelner"));
+        assert!(!matches_folded(b"", b"a"));
+        assert!(matches_folded(b"Strasse", "STRAẞE".as_bytes()));
+    }
+
+    #[test]
+    fn find_umlauts() {
+        let text = "Öl é Grüße GROẞ".as_bytes();
+        assert_eq!(find_next_umlaut(text, 0), Some(0));
+        assert_eq!(find_next_umlaut(text, 1), Some(9));
+        assert_eq!(find_next_umlaut(text, 10), Some(11));
+        assert_eq!(find_next_umlaut(text, 12), Some(18));
+        assert_eq!(find_next_umlaut(text, 19), None);
+        assert_eq!(find_next_umlaut(text, 100), None);
+        assert_eq!(find_next_umlaut(&[b'a', 0xc3], 0), None);
+        assert_eq!(find_next_umlaut(&[0xe1, 0xba], 0), None);
+    }
+
+    fn capped(bytes: &[u8], max: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        fold_din_5007_2_capped(bytes, max, &mut out);
+        out
+    }
+
+    #[test]
+    fn reversible() {
+        assert!(is_fold_reversible("Müller".as_bytes()));
+        assert!(is_fold_reversible("Maßstab".as_bytes()));
+        assert!(is_fold_reversible("Öl Ärmel Übermut".as_bytes()));
+        assert!(is_fold_reversible("üe".as_bytes()));
+        assert!(is_fold_reversible(b"Haus"));
+        assert!(is_fold_reversible(b""));
+    }
+
+    #[test]
+    fn not_reversible() {
+        assert!(!is_fold_reversible(b"Mueller"));
+        assert!(!is_fold_reversible(b"Strasse"));
+        assert!(!is_fold_reversible(b"Blue"));
+        assert!(!is_fold_reversible(b"AEG"));
+        assert!(!is_fold_reversible("Messgerät".as_bytes()));
+        assert!(!is_fold_reversible("sß".as_bytes()));
+        assert!(!is_fold_reversible("Sß".as_bytes()));
+    }
+
+    #[test]
+    fn capped_expansions() {
+        let text = "Grüße".as_bytes();
+        assert_eq!(capped(text, 0), b"");
+        assert_eq!(capped(text, 2), b"Gr");
+        assert_eq!(capped(text, 3), b"Gr");
+        assert_eq!(capped(text, 4), b"Grue");
+        assert_eq!(capped(text, 5), b"Grue");
+        assert_eq!(capped(text, 6), b"Gruess");
+        assert_eq!(capped(text, 7), b"Gruesse");
+        assert_eq!(capped(text, 100), b"Gruesse");
+    }
+
+    #[test]
+    fn capped_multibyte() {
+        let text = "café".as_bytes();
+        assert_eq!(capped(text, 4), b"caf");
+        assert_eq!(capped(text, 5), text);
+    }
+
+    #[test]
+    fn fold_diff() {
+        let diff: Vec<_> = FoldDiff::new("Grüße".as_bytes()).collect();
+        assert_eq!(diff, vec![(2..4, &b"ue"[..]), (4..6, &b"ss"[..])]);
+    }
+
+    #[test]
+    fn fold_diff_capital_sharp_s() {
+        let diff: Vec<_> = FoldDiff::new("STRAẞE".as_bytes()).collect();
+        assert_eq!(diff, vec![(4..7, &b"SS"[..])]);
+    }
+
+    #[test]
+    fn fold_diff_skips_other_characters() {
+        assert_eq!(FoldDiff::new("café │ abc".as_bytes()).count(), 0);
+        let diff: Vec<_> = FoldDiff::new("é Öl".as_bytes()).collect();
+        assert_eq!(diff, vec![(3..5, &b"Oe"[..])]);
+    }
+
+    #[test]
+    fn fold_diff_reconstruct() {
+        let original = "Öl Ärmel Übermut, Straße".as_bytes();
+        let mut folded = Vec::new();
+        let mut last = 0;
+        for (range, replacement) in FoldDiff::new(original) {
+            folded.extend_from_slice(&original[last..range.start]);
+            folded.extend_from_slice(replacement);
+            last = range.end;
+        }
+        folded.extend_from_slice(&original[last..]);
+        assert_eq!(folded, b"Oel Aermel Uebermut, Strasse");
+    }
+
+    #[test]
+    fn fold_trace() {
+        let trace: Vec<_> = FoldTrace::new("aÄ€ẞ".as_bytes()).collect();
+        assert_eq!(
+            trace,
+            vec![
+                (0, 'a', &b"a"[..]),
+                (1, 'Ä', &b"Ae"[..]),
+                (3, '€', "€".as_bytes()),
+                (6, 'ẞ', &b"SS"[..]),
+            ]
+        );
+        let trace: Vec<_> = FoldTrace::new(&[0xc3, b'x']).collect();
+        assert_eq!(
+            trace,
+            vec![(0, '\u{fffd}', &[0xc3][..]), (1, 'x', &b"x"[..])]
+        );
+        assert_eq!(FoldTrace::new(b"").next(), None);
+    }
+
+    #[cfg(feature = "log")]
+    mod log_warnings {
+        use super::fold_strict;
+        use log::{Level, LevelFilter, Log, Metadata, Record};
+        use std::cell::RefCell;
+        use std::sync::Once;
+
+        thread_local! {
+            static WARNINGS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// Collects the warnings of the current thread, as tests run in parallel.
+        struct Collector;
+
+        impl Log for Collector {
+            fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+                metadata.level() <= Level::Warn
+            }
+
+            fn log(&self, record: &Record<'_>) {
+                if self.enabled(record.metadata()) {
+                    WARNINGS.with(|warnings| warnings.borrow_mut().push(record.args().to_string()));
+                }
+            }
+
+            fn flush(&self) {}
+        }
+
+        fn warnings(text: &str) -> Vec<String> {
+            static INIT: Once = Once::new();
+            INIT.call_once(|| {
+                log::set_logger(&Collector).unwrap();
+                log::set_max_level(LevelFilter::Warn);
+            });
+            WARNINGS.with(|warnings| warnings.borrow_mut().clear());
+            let _ = fold_strict(text.as_bytes());
+            WARNINGS.with(|warnings| warnings.borrow().clone())
+        }
+
+        #[test]
+        fn warns_for_unfoldable() {
+            assert_eq!(
+                warnings("Café"),
+                vec!["can't fold 'é' at offset 3 to ASCII".to_string()]
+            );
+            assert_eq!(warnings("Grüße ẞ").len(), 0);
+            assert_eq!(warnings("é 🦀").len(), 2);
+        }
+    }
+
+    #[cfg(feature = "latin")]
+    #[test]
+    fn strict_latin() {
+        assert_eq!(fold_strict("Lüÿt Ÿ".as_bytes()), Ok(b"Lueyt Y".to_vec()));
+        assert_eq!(fold_strict("Cæsar".as_bytes()), Ok(b"Caesar".to_vec()));
+        assert_eq!(fold_strict("Noël".as_bytes()), Err(vec![(2, 'ë')]));
+    }
+
+    #[cfg(not(feature = "latin"))]
+    #[test]
+    fn strict_without_latin() {
+        assert_eq!(fold_strict("Lüÿt".as_bytes()), Err(vec![(3, 'ÿ')]));
+    }
+}
@@ -0,0 +1,155 @@
+//! Validation of input for the in place functions.
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by [`UmlautsInplaceExt::try_inplace_fold`](crate::UmlautsInplaceExt::try_inplace_fold)
+/// for input the in place functions can't handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InplaceError {
+    /// The input starts or ends inside of a UTF-8 character, like a slice split at an umlaut.
+    SplitCharacter {
+        /// The offset of the first byte of the split character within the input.
+        position: usize,
+    },
+}
+
+impl fmt::Display for InplaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InplaceError::SplitCharacter { position } => {
+                write!(f, "input splits the character at offset {}", position)
+            }
+        }
+    }
+}
+
+impl Error for InplaceError {}
+
+/// Error returned by [`UmlautsInplaceExt::fold_ascii_in_place_checked`](crate::UmlautsInplaceExt::fold_ascii_in_place_checked)
+/// if a replacement doesn't fit into the place of its character, like "SS" for 'ẞ'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputTooLarge {
+    pub(crate) position: usize,
+}
+
+impl OutputTooLarge {
+    /// Returns the offset of the first byte of the character within the input.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for OutputTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the replacement of the character at offset {} doesn't fit in place",
+            self.position
+        )
+    }
+}
+
+impl Error for OutputTooLarge {}
+
+/// Result of [`UmlautsInplaceExt::make_utf8_umlauts_to_ascii_reported`](crate::UmlautsInplaceExt::make_utf8_umlauts_to_ascii_reported).
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::prelude::*;
+///
+/// let mut s = "GROẞE Grüße".as_bytes().to_vec();
+/// let report = s.make_utf8_umlauts_to_ascii_reported();
+/// assert_eq!(report.replaced(), 2);
+/// if report.skipped_capital_sharp_s() {
+///     s.fold_ascii_reusing_capacity();
+/// }
+/// assert_eq!(s, b"GROSSE Gruesse");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FoldReport {
+    pub(crate) replaced: usize,
+    pub(crate) skipped_capital_sharp_s: bool,
+}
+
+impl FoldReport {
+    /// Returns the number of replaced umlauts.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+
+    /// Checks if an uppercase ß ('ẞ') was left unchanged, so the text is not fully folded.
+    pub fn skipped_capital_sharp_s(&self) -> bool {
+        self.skipped_capital_sharp_s
+    }
+}
+
+/// Checks that `bytes` neither starts nor ends inside of a character.
+///
+/// See [`UmlautsInplaceExt::try_inplace_fold`](crate::UmlautsInplaceExt::try_inplace_fold).
+pub(crate) fn check_inplace(bytes: &[u8]) -> Result<(), InplaceError> {
+    let is_continuation = |byte: u8| byte & 0xc0 == 0x80;
+    let first = match bytes.first() {
+        Some(&first) => first,
+        None => return Ok(()),
+    };
+    if is_continuation(first) {
+        return Err(InplaceError::SplitCharacter { position: 0 });
+    }
+    // the lead byte of the last character is at most three bytes before the end
+    let start = bytes.len().saturating_sub(4);
+    let lead = (start..bytes.len())
+        .rev()
+        .find(|&i| !is_continuation(bytes[i]))
+        .unwrap_or(start);
+    let len = match bytes[lead] {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xff => 4,
+        _ => 1,
+    };
+    if lead + len > bytes.len() {
+        return Err(InplaceError::SplitCharacter { position: lead });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_inplace, InplaceError, OutputTooLarge};
+
+    #[test]
+    fn valid() {
+        assert_eq!(check_inplace(b""), Ok(()));
+        assert_eq!(check_inplace(b"a"), Ok(()));
+        assert_eq!(check_inplace("Grüße".as_bytes()), Ok(()));
+        assert_eq!(check_inplace("GROẞ".as_bytes()), Ok(()));
+        assert_eq!(check_inplace("🦀".as_bytes()), Ok(()));
+    }
+
+    #[test]
+    fn invalid() {
+        let text = "Grüße".as_bytes();
+        assert_eq!(
+            check_inplace(&text[..3]),
+            Err(InplaceError::SplitCharacter { position: 2 })
+        );
+        assert_eq!(
+            check_inplace(&text[3..]),
+            Err(InplaceError::SplitCharacter { position: 0 })
+        );
+        assert_eq!(
+            check_inplace(&"GROẞ".as_bytes()[..5]),
+            Err(InplaceError::SplitCharacter { position: 3 })
+        );
+        assert_eq!(
+            InplaceError::SplitCharacter { position: 2 }.to_string(),
+            "input splits the character at offset 2"
+        );
+        assert_eq!(
+            OutputTooLarge { position: 3 }.to_string(),
+            "the replacement of the character at offset 3 doesn't fit in place"
+        );
+    }
+}
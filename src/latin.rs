@@ -0,0 +1,53 @@
+//! Folding of latin letters beyond the german alphabet.
+//!
+//! These are not part of german orthography, but are common in borrowed words
+//! and names mixed into german texts.
+
+/// Returns the byte length and the ASCII replacement of the latin letter at `bytes[i..]`.
+///
+/// - 'æ' -> "ae"
+/// - 'Æ' -> "Ae", which [`Transliterator::fold_ascii`](crate::Transliterator::fold_ascii)
+///   expands according to its [`CapitalExpansion`](crate::CapitalExpansion) like "Ae" for 'Ä'
+/// - 'ÿ' -> 'y' and 'Ÿ' -> 'Y', as there is no "ye" convention for the y with diaeresis
+pub(crate) fn latin_at(bytes: &[u8], i: usize) -> Option<(usize, &'static [u8])> {
+    match bytes[i..] {
+        [0xc3, 0xa6, ..] => Some((2, b"ae")),
+        [0xc3, 0x86, ..] => Some((2, b"Ae")),
+        // ÿ
+        [0xc3, 0xbf, ..] => Some((2, b"y")),
+        // Ÿ
+        [0xc5, 0xb8, ..] => Some((2, b"Y")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::latin_at;
+
+    fn latin(text: &str, i: usize) -> Option<&'static [u8]> {
+        latin_at(text.as_bytes(), i).map(|(_, replacement)| replacement)
+    }
+
+    #[test]
+    fn ligatures() {
+        assert_eq!(latin("æ", 0), Some(&b"ae"[..]));
+        assert_eq!(latin("Æble", 0), Some(&b"Ae"[..]));
+        assert_eq!(latin("Æ", 0), Some(&b"Ae"[..]));
+        // the case of the expansion is chosen by the transliterator
+        assert_eq!(latin("ÆBLE", 0), Some(&b"Ae"[..]));
+        assert_eq!(latin("SÆ", 1), Some(&b"Ae"[..]));
+        assert_eq!(latin("ä", 0), None);
+        assert_eq!(latin("L", 0), None);
+        assert_eq!(latin("a", 0), None);
+    }
+
+    #[test]
+    fn y_diaeresis() {
+        assert_eq!(latin("ÿ", 0), Some(&b"y"[..]));
+        assert_eq!(latin("Ÿ", 0), Some(&b"Y"[..]));
+        assert_eq!(latin("ÿ", 1), None);
+        // 'ë' is not folded
+        assert_eq!(latin("ë", 0), None);
+    }
+}
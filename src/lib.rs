@@ -10,6 +10,78 @@ extern crate memchr;
 /// ```
 pub mod prelude;
 
+pub mod bytes;
+
+mod array;
+#[cfg(feature = "tokio")]
+mod async_read;
+#[cfg(all(test, feature = "bstr"))]
+mod byte_string;
+mod case;
+mod custom;
+mod delimited;
+mod filename;
+mod fold;
+mod inplace;
+#[cfg(feature = "latin")]
+mod latin;
+mod mojibake;
+mod name;
+mod normalize;
+#[cfg(feature = "locale_numbers")]
+mod numbers;
+mod owned;
+mod phonetic;
+mod reverse;
+#[cfg(feature = "segmentation")]
+mod segmentation;
+mod sort;
+mod stats;
+mod stream;
+mod string;
+#[cfg(feature = "symbols")]
+mod symbols;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "traditional")]
+mod traditional;
+mod transliterator;
+mod typeable;
+mod umlaut;
+mod utf8;
+mod vec;
+
+pub use crate::array::CapacityError;
+#[cfg(feature = "tokio")]
+pub use crate::async_read::UmlautAsciiAsyncReader;
+pub use crate::custom::{CustomFolder, UnknownUmlautError};
+pub use crate::delimited::{fold_csv_field, fold_delimited, DelimitedStyle};
+pub use crate::filename::FilenameStyle;
+pub use crate::fold::{
+    are_spelling_variants, find_next_umlaut, fold_char_bytes, matches_folded, sharp_s_expansion,
+    FoldDiff, FoldResult, FoldTrace, FoldedTokens, NonAsciiError,
+};
+pub use crate::inplace::{FoldReport, InplaceError, OutputTooLarge};
+pub use crate::name::{folded_levenshtein, NormalizedName};
+pub use crate::owned::UmlautsOwned;
+pub use crate::reverse::{FOLDED_SHARP_S_WORDS, LITERAL_VOWEL_PAIR_WORDS};
+#[cfg(feature = "segmentation")]
+pub use crate::segmentation::GermanGraphemes;
+pub use crate::sort::{cmp_german, german_sort_key, sort_german, SortStyle};
+pub use crate::stats::{UmlautSet, UmlautSetIter, UmlautStats};
+pub use crate::stream::{folded_lines, FoldedLines, UmlautFoldState};
+pub use crate::string::UmlautsStrExt;
+#[cfg(feature = "traditional")]
+pub use crate::traditional::{traditional_sharp_s, TRADITIONAL_SHARP_S_WORDS};
+pub use crate::transliterator::{
+    CapitalExpansion, CasePolicy, SharpSCase, SharpSPolicy, Transliterator,
+};
+pub use crate::typeable::TypeConvention;
+pub use crate::umlaut::{ParseUmlautError, Umlaut};
+pub use crate::vec::UmlautsVecExt;
+
+use crate::fold::next_umlaut;
+
 /// Inplace string processing functions.
 ///
 /// `UnlautsInplaceExt` adds inplace string processing functions for the german "Umlauts"
@@ -60,6 +132,28 @@ pub trait UmlautsInplaceExt {
     /// [`make_ascii_uppercase`]: std::slice::[u8]::make_ascii_uppercase
     fn make_utf8_umlauts_uppercase(&mut self);
 
+    /// Uppercases UTF-8 umlauts, leaving all other bytes unchanged.
+    ///
+    /// Unlike [`make_utf8_umlauts_uppercase`](UmlautsInplaceExt::make_utf8_umlauts_uppercase),
+    /// ASCII letters keep their case:
+    /// - 'ä' -> 'Ä'
+    /// - 'ö' -> 'Ö'
+    /// - 'ü' -> 'Ü'
+    ///
+    /// 'ß' is left unchanged, as 'ẞ' needs an additional byte.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Öl Ärmel Übermut, Grüße".as_bytes().to_vec();
+    /// s.make_umlauts_uppercase_only_in_place();
+    /// assert_eq!("Öl Ärmel Übermut, GrÜße".as_bytes(), s);
+    /// ```
+    fn make_umlauts_uppercase_only_in_place(&mut self);
+
     /// Converts Umlauts to ae, oe, ue, ss, ...
     ///
     /// Maps umlauts according to DIN 5007-2:
@@ -87,6 +181,29 @@ pub trait UmlautsInplaceExt {
     /// ```
     fn make_utf8_umlauts_to_ascii(&mut self);
 
+    /// Converts Umlauts to ae, oe, ue, ss, ... and reports if the text is fully folded.
+    ///
+    /// Maps umlauts like [`make_utf8_umlauts_to_ascii`](UmlautsInplaceExt::make_utf8_umlauts_to_ascii),
+    /// which ignores the uppercase ß ('ẞ'). The returned [`FoldReport`] tells if a 'ẞ'
+    /// was skipped, so the caller can fold the text again with an allocating function like
+    /// [`fold_ascii_reusing_capacity`](crate::UmlautsVecExt::fold_ascii_reusing_capacity).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Grüße".as_bytes().to_vec();
+    /// let report = s.make_utf8_umlauts_to_ascii_reported();
+    /// assert_eq!(s, b"Gruesse");
+    /// assert!(!report.skipped_capital_sharp_s());
+    ///
+    /// let mut s = "GROẞ".as_bytes().to_vec();
+    /// assert!(s.make_utf8_umlauts_to_ascii_reported().skipped_capital_sharp_s());
+    /// ```
+    fn make_utf8_umlauts_to_ascii_reported(&mut self) -> FoldReport;
+
     /// Converts Umlauts to lowercase ae, oe, ue, ss, ... and converts all ASCII characters to
     /// lowercase.
     ///
@@ -142,24 +259,178 @@ pub trait UmlautsInplaceExt {
     /// assert_eq!("OEL AERMEL UEBERMUT".as_bytes(), s);
     /// ```
     fn make_utf8_umlauts_to_uppercase_ascii(&mut self);
+
+    /// Replaces every byte of an umlaut with `mask`.
+    ///
+    /// Masks 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü', 'ß' and 'ẞ', keeping the length of the text.
+    /// All other bytes are left unchanged.
+    /// Returns the number of masked umlauts.
+    ///
+    /// # Panics
+    ///
+    /// The implementation for `str` panics if `mask` is not ASCII,
+    /// as the result would not be valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Müller, Straße".as_bytes().to_vec();
+    /// assert_eq!(s.mask_umlauts_in_place(b'*'), 2);
+    /// assert_eq!(s, b"M**ller, Stra**e");
+    /// ```
+    fn mask_umlauts_in_place(&mut self, mask: u8) -> usize;
+
+    /// Replaces every byte of an umlaut and every ASCII letter with `mask`.
+    ///
+    /// Like [`mask_umlauts_in_place`](UmlautsInplaceExt::mask_umlauts_in_place),
+    /// but masks ASCII letters as well, leaving only digits, punctuation and
+    /// other characters visible.
+    /// Returns the number of masked umlauts and letters.
+    ///
+    /// # Panics
+    ///
+    /// The implementation for `str` panics if `mask` is not ASCII,
+    /// as the result would not be valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Müller, 42".as_bytes().to_vec();
+    /// assert_eq!(s.mask_letters_in_place(b'x'), 6);
+    /// assert_eq!(s, b"xxxxxxx, 42");
+    /// ```
+    fn mask_letters_in_place(&mut self, mask: u8) -> usize;
+
+    /// Asserts that the text is safe to use with the in place functions.
+    ///
+    /// The in place functions require a text that neither starts nor ends
+    /// inside of a UTF-8 character, e.g. a slice split in the middle of an umlaut.
+    /// [`try_inplace_fold`](UmlautsInplaceExt::try_inplace_fold) checks the same
+    /// contract without panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the [`InplaceError`] if the contract is violated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Grüße".as_bytes().to_vec();
+    /// s.assert_inplace_safe_len();
+    /// s.make_utf8_umlauts_to_lowercase_ascii();
+    /// assert_eq!(s, b"gruesse");
+    /// ```
+    fn assert_inplace_safe_len(&self);
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... after validating the text.
+    ///
+    /// Checks the contract of [`assert_inplace_safe_len`](UmlautsInplaceExt::assert_inplace_safe_len)
+    /// and folds like [`make_utf8_umlauts_to_ascii`](UmlautsInplaceExt::make_utf8_umlauts_to_ascii)
+    /// if it holds. Otherwise the text is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::InplaceError;
+    ///
+    /// let mut s = "Grüße".as_bytes().to_vec();
+    /// assert_eq!(s.try_inplace_fold(), Ok(()));
+    /// assert_eq!(s, b"Gruesse");
+    ///
+    /// let mut s = "Grüße".as_bytes()[..3].to_vec();
+    /// assert_eq!(s.try_inplace_fold(), Err(InplaceError::SplitCharacter { position: 2 }));
+    /// ```
+    fn try_inplace_fold(&mut self) -> Result<(), InplaceError>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... or fails if that can't be done in place.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`make_utf8_umlauts_to_ascii`](UmlautsInplaceExt::make_utf8_umlauts_to_ascii),
+    /// but instead of silently skipping the uppercase ß ('ẞ'), whose replacement "SS" is
+    /// shorter, it returns [`OutputTooLarge`] with the position of the first 'ẞ'
+    /// and leaves the text unchanged.
+    /// Returns the number of replaced umlauts otherwise.
+    ///
+    /// Use [`fold_ascii_reusing_capacity`](crate::UmlautsVecExt::fold_ascii_reusing_capacity)
+    /// to fold text containing 'ẞ' without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Grüße".as_bytes().to_vec();
+    /// assert_eq!(s.fold_ascii_in_place_checked(), Ok(2));
+    /// assert_eq!(s, b"Gruesse");
+    ///
+    /// let mut s = "GROẞ".as_bytes().to_vec();
+    /// assert_eq!(s.fold_ascii_in_place_checked().unwrap_err().position(), 3);
+    /// assert_eq!(s, "GROẞ".as_bytes());
+    /// ```
+    fn fold_ascii_in_place_checked(&mut self) -> Result<usize, OutputTooLarge>;
+
+    /// Removes the combining diaeresis of decomposed umlauts, leaving the base vowels.
+    ///
+    /// In decomposed (NFD) text, umlauts are written as 'a', 'o' or 'u' in either case
+    /// followed by a combining diaeresis (U+0308). The diaeresis is removed and the
+    /// following bytes are moved to the front, so the text shrinks by two bytes per umlaut.
+    /// Returns the new length of the text. The bytes after it are set to zero.
+    /// Precomposed umlauts and a diaeresis after any other character are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Ko\u{308}ln".as_bytes().to_vec();
+    /// let len = s.strip_diaeresis_nfd_in_place();
+    /// s.truncate(len);
+    /// assert_eq!(s, b"Koln");
+    /// ```
+    fn strip_diaeresis_nfd_in_place(&mut self) -> usize;
 }
 
 impl UmlautsInplaceExt for [u8] {
     fn make_utf8_umlauts_lowercase(self: &mut [u8]) {
         let mut i = 0;
-        while i < self.len() - 1 {
+        while i + 1 < self.len() {
             let c = self[i];
-            match (c, self[i + 1]) {
-                (b'A'..=b'Z', _) => self[i] = c.to_ascii_lowercase(),
+            // the continuation byte of a processed umlaut is skipped
+            i += match (c, self[i + 1]) {
+                (b'A'..=b'Z', _) => {
+                    self[i] = c.to_ascii_lowercase();
+                    1
+                }
                 // Ä
-                (0xc3, 0x84) => self[i + 1] = 0xa4,
+                (0xc3, 0x84) => {
+                    self[i + 1] = 0xa4;
+                    2
+                }
                 // Ö
-                (0xc3, 0x96) => self[i + 1] = 0xb6,
+                (0xc3, 0x96) => {
+                    self[i + 1] = 0xb6;
+                    2
+                }
                 // Ü
-                (0xc3, 0x9c) => self[i + 1] = 0xbc,
-                _ => {}
-            }
-            i += 1;
+                (0xc3, 0x9c) => {
+                    self[i + 1] = 0xbc;
+                    2
+                }
+                _ => 1,
+            };
         }
         if let Some(c) = self.last_mut() {
             c.make_ascii_lowercase()
@@ -168,29 +439,43 @@ impl UmlautsInplaceExt for [u8] {
 
     fn make_utf8_umlauts_uppercase(self: &mut [u8]) {
         let mut i = 0;
-        while i < self.len() - 1 {
-            let c = self[i];
-            match (c, self[i + 1]) {
-                (b'a'..=b'z', _) => self[i] = c.to_ascii_uppercase(),
-                // ä
-                (0xc3, 0xa4) => self[i + 1] = 0x84,
-                // ö
-                (0xc3, 0xb6) => self[i + 1] = 0x96,
-                // ü
-                (0xc3, 0xbc) => self[i + 1] = 0x9c,
-                _ => {}
+        // chunks without umlauts are uppercased at once, which the compiler vectorizes
+        while i + UPPERCASE_CHUNK <= self.len() {
+            let chunk_end = i + UPPERCASE_CHUNK;
+            if self[i..chunk_end].is_ascii() {
+                self[i..chunk_end].make_ascii_uppercase();
+                i = chunk_end;
+            } else {
+                // an umlaut may end in the next chunk
+                while i < chunk_end {
+                    i += uppercase_char_at(self, i);
+                }
+            }
+        }
+        make_utf8_umlauts_uppercase_scalar(self, i);
+    }
+
+    fn make_umlauts_uppercase_only_in_place(&mut self) {
+        let mut i = 0;
+        while let Some(offset) = memchr::memchr(0xc3, &self[i..]) {
+            let start = i + offset;
+            i = start + 1;
+            if let Some(next) = self.get_mut(start + 1) {
+                // ä ö ü
+                if let 0xa4 | 0xb6 | 0xbc = *next {
+                    *next -= 0x20;
+                    i += 1;
+                }
             }
-            i += 1;
         }
-        if let Some(c) = self.last_mut() {
-            c.make_ascii_uppercase()
-        };
     }
 
     fn make_utf8_umlauts_to_ascii(&mut self) {
         let mut i = 0;
-        while i < self.len() - 1 {
-            if let Some(next_i) = memchr::memchr(0xc3, &self[..self.len() - 1]) {
+        while i + 1 < self.len() {
+            // the last byte can't start an umlaut, so it is not searched
+            if let Some(offset) = memchr::memchr(0xc3, &self[i..self.len() - 1]) {
+                let next_i = i + offset;
                 if let Some(replacement) = match self[next_i + 1] {
                     0xa4 => Some((b'a', b'e')), // ae
                     0xb6 => Some((b'o', b'e')), // oe
@@ -203,9 +488,10 @@ impl UmlautsInplaceExt for [u8] {
                 } {
                     self[next_i] = replacement.0;
                     self[next_i + 1] = replacement.1;
-                    i = next_i + 1;
-                } else {
                     i = next_i + 2;
+                } else {
+                    // the next byte may start an umlaut if this one is malformed
+                    i = next_i + 1;
                 }
             } else {
                 break;
@@ -213,9 +499,24 @@ impl UmlautsInplaceExt for [u8] {
         }
     }
 
+    fn make_utf8_umlauts_to_ascii_reported(&mut self) -> FoldReport {
+        let mut report = FoldReport::default();
+        let mut last = 0;
+        while let Some((range, replacement)) = next_umlaut(self, last) {
+            last = range.end;
+            if replacement.len() == range.len() {
+                self[range].copy_from_slice(replacement);
+                report.replaced += 1;
+            } else {
+                report.skipped_capital_sharp_s = true;
+            }
+        }
+        report
+    }
+
     fn make_utf8_umlauts_to_lowercase_ascii(&mut self) {
         let mut i = 0;
-        while i < self.len() - 1 {
+        while i + 1 < self.len() {
             if self[i] == 0xc3 {
                 if let Some(replacement) = match self[i + 1] {
                     0xa4 => Some((b'a', b'e')), // ae
@@ -229,10 +530,9 @@ impl UmlautsInplaceExt for [u8] {
                 } {
                     self[i] = replacement.0;
                     self[i + 1] = replacement.1;
-                    i += 1;
-                } else {
-                    i += 2;
                 }
+                // the continuation byte is skipped
+                i += 2;
             } else {
                 self[i].make_ascii_lowercase();
                 i += 1;
@@ -246,7 +546,7 @@ impl UmlautsInplaceExt for [u8] {
 
     fn make_utf8_umlauts_to_uppercase_ascii(&mut self) {
         let mut i = 0;
-        while i < self.len() - 1 {
+        while i + 1 < self.len() {
             if self[i] == 0xc3 {
                 if let Some(replacement) = match self[i + 1] {
                     0xa4 => Some((b'A', b'E')), // AE
@@ -260,10 +560,9 @@ impl UmlautsInplaceExt for [u8] {
                 } {
                     self[i] = replacement.0;
                     self[i + 1] = replacement.1;
-                    i += 1;
-                } else {
-                    i += 2;
                 }
+                // the continuation byte is skipped
+                i += 2;
             } else {
                 self[i].make_ascii_uppercase();
                 i += 1;
@@ -274,46 +573,202 @@ impl UmlautsInplaceExt for [u8] {
             byte.make_ascii_uppercase();
         }
     }
+
+    fn mask_umlauts_in_place(&mut self, mask: u8) -> usize {
+        let mut count = 0;
+        let mut last = 0;
+        while let Some((range, _)) = next_umlaut(self, last) {
+            last = range.end;
+            for byte in &mut self[range] {
+                *byte = mask;
+            }
+            count += 1;
+        }
+        count
+    }
+
+    fn mask_letters_in_place(&mut self, mask: u8) -> usize {
+        let mut count = 0;
+        let mut last = 0;
+        while let Some((range, _)) = next_umlaut(self, last) {
+            count += mask_ascii_letters(&mut self[last..range.start], mask);
+            last = range.end;
+            for byte in &mut self[range] {
+                *byte = mask;
+            }
+            count += 1;
+        }
+        count + mask_ascii_letters(&mut self[last..], mask)
+    }
+
+    fn assert_inplace_safe_len(&self) {
+        if let Err(err) = inplace::check_inplace(self) {
+            panic!("{}", err);
+        }
+    }
+
+    fn try_inplace_fold(&mut self) -> Result<(), InplaceError> {
+        inplace::check_inplace(self)?;
+        self.make_utf8_umlauts_to_ascii();
+        Ok(())
+    }
+
+    fn fold_ascii_in_place_checked(&mut self) -> Result<usize, OutputTooLarge> {
+        let mut last = 0;
+        while let Some((range, replacement)) = next_umlaut(self, last) {
+            if replacement.len() != range.len() {
+                return Err(OutputTooLarge {
+                    position: range.start,
+                });
+            }
+            last = range.end;
+        }
+
+        let mut count = 0;
+        let mut last = 0;
+        while let Some((range, replacement)) = next_umlaut(self, last) {
+            last = range.end;
+            self[range].copy_from_slice(replacement);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn strip_diaeresis_nfd_in_place(&mut self) -> usize {
+        normalize::strip_diaeresis_in_place(self)
+    }
+}
+
+/// Number of bytes [`UmlautsInplaceExt::make_utf8_umlauts_uppercase`] checks for umlauts at once.
+const UPPERCASE_CHUNK: usize = 16;
+
+/// Uppercases the ASCII letter or umlaut at `bytes[i]` and returns the number of processed bytes.
+fn uppercase_char_at(bytes: &mut [u8], i: usize) -> usize {
+    match (bytes[i], bytes.get(i + 1)) {
+        (c @ b'a'..=b'z', _) => {
+            bytes[i] = c.to_ascii_uppercase();
+            1
+        }
+        // ä ö ü, the continuation byte is skipped
+        (0xc3, Some(&next @ (0xa4 | 0xb6 | 0xbc))) => {
+            bytes[i + 1] = next - 0x20;
+            2
+        }
+        _ => 1,
+    }
+}
+
+/// Uppercases ASCII letters and umlauts of `bytes` starting at `start` one by one.
+///
+/// Not part of the public API, only public for the benchmarks.
+#[doc(hidden)]
+pub fn make_utf8_umlauts_uppercase_scalar(bytes: &mut [u8], start: usize) {
+    let mut i = start;
+    while i < bytes.len() {
+        i += uppercase_char_at(bytes, i);
+    }
+}
+
+/// Replaces every ASCII letter in `bytes` with `mask` and returns their number.
+fn mask_ascii_letters(bytes: &mut [u8], mask: u8) -> usize {
+    let mut count = 0;
+    for byte in bytes.iter_mut().filter(|byte| byte.is_ascii_alphabetic()) {
+        *byte = mask;
+        count += 1;
+    }
+    count
+}
+
+/// Runs `f` on the bytes of `text`, asserting in debug builds that it kept valid UTF-8.
+///
+/// All in place functions for `str` go through here, so a mapping that breaks UTF-8
+/// is caught in tests instead of causing undefined behavior later on.
+///
+/// # Safety
+///
+/// `f` has to leave the bytes valid UTF-8. Release builds don't check this.
+#[cfg(feature = "unsafe")]
+unsafe fn with_bytes_mut<R, F: FnOnce(&mut [u8]) -> R>(text: &mut str, f: F) -> R {
+    let result = f(text.as_bytes_mut());
+    debug_assert_utf8(text.as_bytes());
+    result
+}
+
+/// Asserts in debug builds that `bytes` is valid UTF-8.
+#[cfg(feature = "unsafe")]
+fn debug_assert_utf8(bytes: &[u8]) {
+    debug_assert!(
+        std::str::from_utf8(bytes).is_ok(),
+        "in place operation produced invalid UTF-8"
+    );
 }
 
+// SAFETY: the in place functions only replace whole characters with ASCII or
+// characters of the same length, which keeps valid UTF-8 valid
 #[cfg(feature = "unsafe")]
 impl UmlautsInplaceExt for str {
     fn make_utf8_umlauts_lowercase(&mut self) {
-        unsafe {
-            self.as_bytes_mut().make_utf8_umlauts_lowercase();
-        }
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_utf8_umlauts_lowercase()) };
     }
 
     fn make_utf8_umlauts_uppercase(&mut self) {
-        unsafe {
-            self.as_bytes_mut().make_utf8_umlauts_uppercase();
-        }
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_utf8_umlauts_uppercase()) };
+    }
+
+    fn make_umlauts_uppercase_only_in_place(&mut self) {
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_umlauts_uppercase_only_in_place()) };
     }
 
     fn make_utf8_umlauts_to_ascii(&mut self) {
-        unsafe {
-            self.as_bytes_mut().make_utf8_umlauts_to_ascii();
-        }
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_utf8_umlauts_to_ascii()) };
+    }
+
+    fn make_utf8_umlauts_to_ascii_reported(&mut self) -> FoldReport {
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_utf8_umlauts_to_ascii_reported()) }
     }
 
     fn make_utf8_umlauts_to_lowercase_ascii(&mut self) {
-        unsafe {
-            self.as_bytes_mut().make_utf8_umlauts_to_lowercase_ascii();
-        }
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_utf8_umlauts_to_lowercase_ascii()) };
     }
 
     fn make_utf8_umlauts_to_uppercase_ascii(&mut self) {
-        unsafe {
-            self.as_bytes_mut().make_utf8_umlauts_to_uppercase_ascii();
-        }
+        unsafe { with_bytes_mut(self, |bytes| bytes.make_utf8_umlauts_to_uppercase_ascii()) };
+    }
+
+    fn mask_umlauts_in_place(&mut self, mask: u8) -> usize {
+        assert!(mask.is_ascii(), "mask {:#04x} is not ASCII", mask);
+        unsafe { with_bytes_mut(self, |bytes| bytes.mask_umlauts_in_place(mask)) }
+    }
+
+    fn mask_letters_in_place(&mut self, mask: u8) -> usize {
+        assert!(mask.is_ascii(), "mask {:#04x} is not ASCII", mask);
+        unsafe { with_bytes_mut(self, |bytes| bytes.mask_letters_in_place(mask)) }
+    }
+
+    fn assert_inplace_safe_len(&self) {
+        self.as_bytes().assert_inplace_safe_len();
+    }
+
+    fn try_inplace_fold(&mut self) -> Result<(), InplaceError> {
+        unsafe { with_bytes_mut(self, |bytes| bytes.try_inplace_fold()) }
+    }
+
+    fn fold_ascii_in_place_checked(&mut self) -> Result<usize, OutputTooLarge> {
+        unsafe { with_bytes_mut(self, |bytes| bytes.fold_ascii_in_place_checked()) }
+    }
+
+    fn strip_diaeresis_nfd_in_place(&mut self) -> usize {
+        // the freed bytes at the end are zeroed, so the text stays valid UTF-8
+        unsafe { with_bytes_mut(self, |bytes| bytes.strip_diaeresis_nfd_in_place()) }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::UmlautsInplaceExt;
+    use crate::{FoldReport, UmlautsInplaceExt, UmlautsOwned};
 
     #[test]
+    #[allow(clippy::needless_as_bytes)]
     fn char_length() {
         assert_eq!("ä".as_bytes().len(), 2);
         assert_eq!("ö".as_bytes().len(), 2);
@@ -344,27 +799,244 @@ mod tests {
         assert_eq!(text, "ÄÖÜÄÖÜABCDABCD".as_bytes());
     }
 
+    #[test]
+    fn make_utf8_consecutive_umlauts() {
+        let mut text = "ÄÄÖÖÜÜZÄzäY".as_bytes().to_vec();
+        text.make_utf8_umlauts_lowercase();
+        assert_eq!(text, "ääööüüzäzäy".as_bytes());
+        text.make_utf8_umlauts_uppercase();
+        assert_eq!(text, "ÄÄÖÖÜÜZÄZÄY".as_bytes());
+    }
+
     #[test]
     fn make_utf8_umlauts_to_ascii_bytes() {
         let mut text = "ÄÖÜäöüABCDabcd".as_bytes().to_vec();
         text.make_utf8_umlauts_to_ascii();
+        assert_eq!(text.ensure_folded_ascii(), Ok(()));
         assert_eq!(text, "AeOeUeaeoeueABCDabcd".as_bytes());
     }
 
+    #[test]
+    fn make_utf8_umlauts_to_ascii_two_bytes() {
+        let f = <[u8]>::make_utf8_umlauts_to_ascii;
+        assert_eq!(apply("ä", f), "ae");
+        assert_eq!(apply("aä", f), "aae");
+        assert_eq!(apply("äa", f), "aea");
+        assert_eq!(apply("ää", f), "aeae");
+        assert_eq!(apply("äöü", f), "aeoeue");
+        assert_eq!(apply("", f), "");
+        assert_eq!(apply("a", f), "a");
+    }
+
+    #[test]
+    fn make_utf8_umlauts_to_ascii_other_characters() {
+        let f = <[u8]>::make_utf8_umlauts_to_ascii;
+        assert_eq!(apply("éab", f), "éab");
+        assert_eq!(apply("éä café Müller", f), "éae café Mueller");
+        assert_eq!(apply("ẞ", f), "ẞ");
+        let mut text = vec![0xc3, 0xc3, 0xa4, 0xc3];
+        text.make_utf8_umlauts_to_ascii();
+        assert_eq!(text, [0xc3, b'a', b'e', 0xc3]);
+    }
+
     #[test]
     fn make_utf8_umlauts_to_lowercase_ascii_bytes() {
         let mut text = "ÄÖÜäöüABCDabcd".as_bytes().to_vec();
         text.make_utf8_umlauts_to_lowercase_ascii();
+        assert_eq!(text.ensure_folded_ascii(), Ok(()));
         assert_eq!(text, "aeoeueaeoeueabcdabcd".as_bytes());
     }
 
+    #[test]
+    fn make_utf8_umlauts_to_lowercase_ascii_sharp_s_at_end() {
+        let mut text = "Straße".as_bytes().to_vec();
+        text.make_utf8_umlauts_to_lowercase_ascii();
+        assert_eq!(text, b"strasse");
+
+        let mut text = "STRAß".as_bytes().to_vec();
+        text.make_utf8_umlauts_to_lowercase_ascii();
+        assert_eq!(text, b"strass");
+
+        let mut text = "ß".as_bytes().to_vec();
+        text.make_utf8_umlauts_to_lowercase_ascii();
+        assert_eq!(text, b"ss");
+    }
+
     #[test]
     fn make_utf8_umlauts_to_uppercase_ascii_bytes() {
         let mut text = "ÄÖÜäöüABCDabcd".as_bytes().to_vec();
         text.make_utf8_umlauts_to_uppercase_ascii();
+        assert_eq!(text.ensure_folded_ascii(), Ok(()));
         assert_eq!(text, "AEOEUEAEOEUEABCDABCD".as_bytes());
     }
 
+    /// Applies `f` to a copy of `text` and returns the result as string.
+    fn apply(text: &str, f: fn(&mut [u8])) -> String {
+        let mut bytes = text.as_bytes().to_vec();
+        f(&mut bytes);
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn single_umlaut() {
+        let cases = [
+            ("ä", "ä", "Ä", "ae", "ae", "AE"),
+            ("ö", "ö", "Ö", "oe", "oe", "OE"),
+            ("ü", "ü", "Ü", "ue", "ue", "UE"),
+            ("Ä", "ä", "Ä", "Ae", "ae", "AE"),
+            ("Ö", "ö", "Ö", "Oe", "oe", "OE"),
+            ("Ü", "ü", "Ü", "Ue", "ue", "UE"),
+            ("ß", "ß", "ß", "ss", "ss", "SS"),
+        ];
+        for &(text, lower, upper, ascii, lower_ascii, upper_ascii) in cases.iter() {
+            assert_eq!(apply(text, <[u8]>::make_utf8_umlauts_lowercase), lower);
+            assert_eq!(apply(text, <[u8]>::make_utf8_umlauts_uppercase), upper);
+            assert_eq!(apply(text, <[u8]>::make_utf8_umlauts_to_ascii), ascii);
+            assert_eq!(
+                apply(text, <[u8]>::make_utf8_umlauts_to_lowercase_ascii),
+                lower_ascii
+            );
+            assert_eq!(
+                apply(text, <[u8]>::make_utf8_umlauts_to_uppercase_ascii),
+                upper_ascii
+            );
+        }
+    }
+
+    #[test]
+    fn uppercase_chunks_match_scalar() {
+        use super::make_utf8_umlauts_uppercase_scalar;
+        use crate::test_util::Xorshift;
+
+        // bytes around the interesting values, so umlauts often cross chunk boundaries
+        let alphabet = [
+            b'a', b'z', b'A', b'{', b' ', 0xc3, 0xa4, 0xb6, 0xbc, 0x84, 0x9f, 0xe1,
+        ];
+        let mut rng = Xorshift::new(0x1234_5678);
+        for len in 0..200 {
+            let text: Vec<u8> = (0..len).map(|_| *rng.pick(&alphabet)).collect();
+            let mut chunked = text.clone();
+            chunked.make_utf8_umlauts_uppercase();
+            let mut scalar = text.clone();
+            make_utf8_umlauts_uppercase_scalar(&mut scalar, 0);
+            assert_eq!(chunked, scalar, "{:x?}", text);
+        }
+
+        let text = "grüße aus köln, schöne grüße aus münchen und überall";
+        assert_eq!(
+            apply(text, <[u8]>::make_utf8_umlauts_uppercase),
+            "GRÜßE AUS KÖLN, SCHÖNE GRÜßE AUS MÜNCHEN UND ÜBERALL"
+        );
+        let text = "abcdefghijklmnoä";
+        assert_eq!(
+            apply(text, <[u8]>::make_utf8_umlauts_uppercase),
+            "ABCDEFGHIJKLMNOÄ"
+        );
+        assert_eq!(apply("", <[u8]>::make_utf8_umlauts_uppercase), "");
+    }
+
+    #[test]
+    fn uppercase_only_umlauts() {
+        let upper = |text: &str| apply(text, <[u8]>::make_umlauts_uppercase_only_in_place);
+        assert_eq!(upper("äöü ÄÖÜ abc ß"), "ÄÖÜ ÄÖÜ abc ß");
+        assert_eq!(upper("schön"), "schÖn");
+        assert_eq!(upper(""), "");
+        assert_eq!(upper("é"), "é");
+
+        let mut text = vec![b'a', 0xc3];
+        text.make_umlauts_uppercase_only_in_place();
+        assert_eq!(text, [b'a', 0xc3]);
+    }
+
+    #[test]
+    fn mask_umlauts() {
+        let mut text = "Öl, GROẞE Grüße! café".as_bytes().to_vec();
+        assert_eq!(text.mask_umlauts_in_place(b'#'), 4);
+        assert_eq!(text, "##l, GRO###E Gr####e! café".as_bytes());
+
+        let mut text = vec![b'a', 0xc3, b'b', 0xc3];
+        assert_eq!(text.mask_umlauts_in_place(b'#'), 0);
+        assert_eq!(text, [b'a', 0xc3, b'b', 0xc3]);
+    }
+
+    #[test]
+    fn mask_letters() {
+        let mut text = "Öl, 3 Grüße, café".as_bytes().to_vec();
+        assert_eq!(text.mask_letters_in_place(b'_'), 10);
+        assert_eq!(text, "___, 3 _______, ___é".as_bytes());
+        let mut text = Vec::new();
+        assert_eq!(text.mask_letters_in_place(b'_'), 0);
+    }
+
+    #[test]
+    fn try_inplace_fold() {
+        use crate::InplaceError;
+
+        let mut text = "Öl, Straße".as_bytes().to_vec();
+        assert_eq!(text.try_inplace_fold(), Ok(()));
+        assert_eq!(text, b"Oel, Strasse");
+        let mut text: Vec<u8> = Vec::new();
+        assert_eq!(text.try_inplace_fold(), Ok(()));
+        let mut text = "Öl".as_bytes().to_vec();
+        assert_eq!(
+            text[1..].try_inplace_fold(),
+            Err(InplaceError::SplitCharacter { position: 0 })
+        );
+        assert_eq!(text, "Öl".as_bytes());
+    }
+
+    #[test]
+    fn fold_ascii_in_place_checked() {
+        use crate::OutputTooLarge;
+
+        let mut text = "Öl, Straße".as_bytes().to_vec();
+        assert_eq!(text.fold_ascii_in_place_checked(), Ok(2));
+        assert_eq!(text, b"Oel, Strasse");
+        assert_eq!(text.ensure_folded_ascii(), Ok(()));
+        let mut text = "Grüße, GROẞE".as_bytes().to_vec();
+        assert_eq!(
+            text.fold_ascii_in_place_checked(),
+            Err(OutputTooLarge { position: 12 })
+        );
+        assert_eq!(text, "Grüße, GROẞE".as_bytes());
+        let mut text: Vec<u8> = Vec::new();
+        assert_eq!(text.fold_ascii_in_place_checked(), Ok(0));
+        let mut text = [b'a', 0xc3];
+        assert_eq!(text.fold_ascii_in_place_checked(), Ok(0));
+    }
+
+    #[test]
+    fn empty_input() {
+        let mut text: Vec<u8> = Vec::new();
+        text.assert_inplace_safe_len();
+        text.make_utf8_umlauts_lowercase();
+        text.make_utf8_umlauts_to_lowercase_ascii();
+        text.make_utf8_umlauts_to_uppercase_ascii();
+        assert_eq!(text, b"");
+    }
+
+    #[test]
+    #[should_panic(expected = "splits the character at offset 1")]
+    fn assert_inplace_safe_len_split() {
+        "aü".as_bytes()[..2].assert_inplace_safe_len();
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe")]
+    fn mask_string() {
+        let mut text = "Grüße".to_string();
+        assert_eq!(text.mask_umlauts_in_place(b'*'), 2);
+        assert_eq!(text, "Gr****e");
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe")]
+    #[should_panic(expected = "is not ASCII")]
+    fn mask_string_non_ascii() {
+        let mut text = "Grüße".to_string();
+        text.mask_umlauts_in_place(0xc3);
+    }
+
     #[test]
     #[cfg(feature = "unsafe")]
     fn make_utf8_string() {
@@ -373,5 +1045,94 @@ mod tests {
         assert_eq!(text, "äöüäöüabcdabcd");
         text.make_utf8_umlauts_uppercase();
         assert_eq!(text, "ÄÖÜÄÖÜABCDABCD");
+        let mut text = "grün".to_string();
+        text.make_umlauts_uppercase_only_in_place();
+        assert_eq!(text, "grÜn");
+    }
+
+    #[test]
+    fn make_utf8_reported() {
+        for text in ["GROẞE Grüße", "Grüße", "ẞ", "ẞẞ ß", "Köln", "abc", "ä"].iter()
+        {
+            let mut reported = text.as_bytes().to_vec();
+            let report = reported.make_utf8_umlauts_to_ascii_reported();
+            assert_eq!(
+                report.skipped_capital_sharp_s(),
+                text.contains('ẞ'),
+                "{}",
+                text
+            );
+            let mut folded = text.as_bytes().to_vec();
+            folded.make_utf8_umlauts_to_ascii();
+            assert_eq!(reported, folded);
+        }
+        let mut text = "ÄÖÜäöüßẞ".as_bytes().to_vec();
+        assert_eq!(text.make_utf8_umlauts_to_ascii_reported().replaced(), 7);
+        let report = Vec::new().make_utf8_umlauts_to_ascii_reported();
+        assert_eq!(report, FoldReport::default());
+    }
+
+    #[test]
+    fn strip_diaeresis_nfd() {
+        let mut text = "o\u{308}".as_bytes().to_vec();
+        assert_eq!(text.strip_diaeresis_nfd_in_place(), 1);
+        assert_eq!(text, [b'o', 0, 0]);
+        let mut text = "Gru\u{308}n, Öl".as_bytes().to_vec();
+        let len = text.strip_diaeresis_nfd_in_place();
+        assert_eq!(&text[..len], "Grun, Öl".as_bytes());
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe")]
+    fn string_stays_utf8() {
+        let texts = [
+            "ÄÖÜäöüßẞ",
+            "GROẞE Grüße",
+            "a\u{308}",
+            "Ä",
+            "ẞ",
+            "€ café 🦀",
+            "x",
+        ];
+        let folds: [fn(&mut str); 6] = [
+            str::make_utf8_umlauts_lowercase,
+            str::make_utf8_umlauts_uppercase,
+            str::make_umlauts_uppercase_only_in_place,
+            str::make_utf8_umlauts_to_ascii,
+            str::make_utf8_umlauts_to_lowercase_ascii,
+            str::make_utf8_umlauts_to_uppercase_ascii,
+        ];
+        for text in texts.iter() {
+            for fold in folds.iter() {
+                let mut text = text.to_string();
+                fold(&mut text);
+                assert!(std::str::from_utf8(text.as_bytes()).is_ok());
+            }
+            let mut string = text.to_string();
+            string.make_utf8_umlauts_to_ascii_reported();
+            string.mask_umlauts_in_place(b'*');
+            string.mask_letters_in_place(b'x');
+            let mut string = text.to_string();
+            let _ = string.try_inplace_fold();
+            let _ = string.fold_ascii_in_place_checked();
+            string.strip_diaeresis_nfd_in_place();
+            assert!(std::str::from_utf8(string.as_bytes()).is_ok());
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "unsafe", debug_assertions))]
+    #[should_panic(expected = "in place operation produced invalid UTF-8")]
+    fn string_broken_utf8() {
+        super::debug_assert_utf8(&[0xc3, b'a']);
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe")]
+    fn strip_diaeresis_nfd_string() {
+        let mut text = "A\u{308}rger €".to_string();
+        let len = text.strip_diaeresis_nfd_in_place();
+        text.truncate(len);
+        assert_eq!(text, "Arger €");
     }
 }
@@ -0,0 +1,101 @@
+//! Repair of umlauts that have been encoded as UTF-8 twice.
+
+/// The UTF-8 encoded umlauts and their double encoded forms.
+///
+/// A double encoded umlaut is its UTF-8 encoding read as Latin-1 or Windows-1252
+/// and encoded as UTF-8 again, like 'ü' (0xc3 0xbc) read as "Ã¼".
+/// The forms only differ for bytes that Windows-1252 maps to other characters than Latin-1.
+const DOUBLE_ENCODED: &[(&[u8], &[u8])] = &[
+    // ä: "Ã¤"
+    ("ä".as_bytes(), &[0xc3, 0x83, 0xc2, 0xa4]),
+    // ö: "Ã¶"
+    ("ö".as_bytes(), &[0xc3, 0x83, 0xc2, 0xb6]),
+    // ü: "Ã¼"
+    ("ü".as_bytes(), &[0xc3, 0x83, 0xc2, 0xbc]),
+    // Ä: "Ã\u{84}" in Latin-1, "Ã„" in Windows-1252
+    ("Ä".as_bytes(), &[0xc3, 0x83, 0xc2, 0x84]),
+    ("Ä".as_bytes(), &[0xc3, 0x83, 0xe2, 0x80, 0x9e]),
+    // Ö: "Ã\u{96}" in Latin-1, "Ã–" in Windows-1252
+    ("Ö".as_bytes(), &[0xc3, 0x83, 0xc2, 0x96]),
+    ("Ö".as_bytes(), &[0xc3, 0x83, 0xe2, 0x80, 0x93]),
+    // Ü: "Ã\u{9c}" in Latin-1, "Ãœ" in Windows-1252
+    ("Ü".as_bytes(), &[0xc3, 0x83, 0xc2, 0x9c]),
+    ("Ü".as_bytes(), &[0xc3, 0x83, 0xc5, 0x93]),
+    // ß: "Ã\u{9f}" in Latin-1, "ÃŸ" in Windows-1252
+    ("ß".as_bytes(), &[0xc3, 0x83, 0xc2, 0x9f]),
+    ("ß".as_bytes(), &[0xc3, 0x83, 0xc5, 0xb8]),
+    // ẞ: "áº\u{9e}" in Latin-1, "áºž" in Windows-1252
+    ("ẞ".as_bytes(), &[0xc3, 0xa1, 0xc2, 0xba, 0xc2, 0x9e]),
+    ("ẞ".as_bytes(), &[0xc3, 0xa1, 0xc2, 0xba, 0xc5, 0xbe]),
+];
+
+/// Replaces double encoded umlauts in `bytes` with their UTF-8 encoding.
+///
+/// See [`UmlautsOwned::fix_double_encoded_umlauts`](crate::UmlautsOwned::fix_double_encoded_umlauts).
+pub(crate) fn fix_double_encoded(bytes: &[u8]) -> Vec<u8> {
+    let mut fixed = Vec::with_capacity(bytes.len());
+    let mut last = 0;
+    let mut from = 0;
+    while let Some(offset) = memchr::memchr(0xc3, &bytes[from..]) {
+        let i = from + offset;
+        let broken = DOUBLE_ENCODED
+            .iter()
+            .find(|(_, broken)| bytes[i..].starts_with(broken));
+        match broken {
+            Some((umlaut, broken)) => {
+                fixed.extend_from_slice(&bytes[last..i]);
+                fixed.extend_from_slice(umlaut);
+                last = i + broken.len();
+                from = last;
+            }
+            None => from = i + 1,
+        }
+    }
+    fixed.extend_from_slice(&bytes[last..]);
+    fixed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fix_double_encoded, DOUBLE_ENCODED};
+
+    /// Encodes `text` as UTF-8, reads it as Latin-1 and encodes the result as UTF-8.
+    fn double_encode_latin_1(text: &str) -> String {
+        text.bytes().map(char::from).collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        for umlaut in "äöüÄÖÜßẞ".chars() {
+            let umlaut = umlaut.to_string();
+            let broken = double_encode_latin_1(&umlaut);
+            assert_eq!(
+                fix_double_encoded(broken.as_bytes()),
+                umlaut.as_bytes(),
+                "{:?}",
+                broken
+            );
+        }
+        for (umlaut, broken) in DOUBLE_ENCODED.iter() {
+            assert_eq!(fix_double_encoded(broken), *umlaut);
+        }
+    }
+
+    #[test]
+    fn windows_1252() {
+        assert_eq!(
+            fix_double_encoded("GrÃ¼ÃŸe aus Ãœberlingen, Ã„rger, Ã–l".as_bytes()),
+            "Grüße aus Überlingen, Ärger, Öl".as_bytes()
+        );
+    }
+
+    #[test]
+    fn other_text_unchanged() {
+        for text in ["Grüße", "Ã©", "Ã", "Ãx", "café", ""].iter() {
+            assert_eq!(fix_double_encoded(text.as_bytes()), text.as_bytes());
+        }
+        // 'é' double encoded is not a german umlaut
+        let broken = double_encode_latin_1("é");
+        assert_eq!(fix_double_encoded(broken.as_bytes()), broken.as_bytes());
+    }
+}
@@ -0,0 +1,207 @@
+//! Names compared by their folded form.
+use crate::fold::fold_din_5007_2;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A name that is compared, ordered and hashed by its DIN 5007-2 folded lowercase form.
+///
+/// Spelling variants like "Müller", "Mueller" and "MÜLLER" are equal, so
+/// `NormalizedName` can be used as a key in a `BTreeMap` or `HashMap`.
+/// Names are ordered like in a phone book (DIN 5007-2), e.g. "Müller" sorts as "mueller".
+/// The original spelling is kept and returned by [`as_str`](NormalizedName::as_str).
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::NormalizedName;
+/// use std::collections::BTreeMap;
+///
+/// let mut phone_book = BTreeMap::new();
+/// phone_book.insert(NormalizedName::new("Müller"), 1);
+/// phone_book.insert(NormalizedName::new("Mueller"), 2);
+/// phone_book.insert(NormalizedName::new("Muff"), 3);
+/// assert_eq!(phone_book.len(), 2);
+/// assert_eq!(phone_book[&NormalizedName::new("MÜLLER")], 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NormalizedName {
+    name: String,
+    folded: Vec<u8>,
+}
+
+impl NormalizedName {
+    /// Creates a `NormalizedName`, computing its folded form once.
+    pub fn new(name: impl Into<String>) -> NormalizedName {
+        let name = name.into();
+        let folded = fold_lowercase(&name);
+        NormalizedName { name, folded }
+    }
+
+    /// Returns the name in its original spelling.
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the folded lowercase form used for comparisons.
+    pub fn folded(&self) -> &[u8] {
+        &self.folded
+    }
+}
+
+impl PartialEq for NormalizedName {
+    fn eq(&self, other: &NormalizedName) -> bool {
+        self.folded == other.folded
+    }
+}
+
+impl Eq for NormalizedName {}
+
+impl PartialOrd for NormalizedName {
+    fn partial_cmp(&self, other: &NormalizedName) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NormalizedName {
+    fn cmp(&self, other: &NormalizedName) -> Ordering {
+        self.folded.cmp(&other.folded)
+    }
+}
+
+impl Hash for NormalizedName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.folded.hash(state);
+    }
+}
+
+impl fmt::Display for NormalizedName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl From<&str> for NormalizedName {
+    fn from(name: &str) -> NormalizedName {
+        NormalizedName::new(name)
+    }
+}
+
+impl From<String> for NormalizedName {
+    fn from(name: String) -> NormalizedName {
+        NormalizedName::new(name)
+    }
+}
+
+/// Lowercases `name` like `str::to_lowercase` and folds it according to DIN 5007-2.
+fn fold_lowercase(name: &str) -> Vec<u8> {
+    let lower = name.to_lowercase();
+    let mut folded = Vec::with_capacity(lower.len());
+    fold_din_5007_2(lower.as_bytes(), &mut folded);
+    folded
+}
+
+/// Computes the Levenshtein distance between the folded lowercase forms of `a` and `b`.
+///
+/// Both inputs are lowercased like for [`NormalizedName`] and folded according to
+/// DIN 5007-2 first, so spelling variants like "Müller" and "Mueller" have a distance
+/// of 0, and "Müller" and "Möller" differ by one substitution of "ue" and "oe". The
+/// distance counts byte wise insertions, deletions and substitutions of the folded
+/// forms. Invalid UTF-8 is replaced with U+FFFD.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::folded_levenshtein;
+///
+/// assert_eq!(folded_levenshtein("Müller".as_bytes(), b"MUELLER"), 0);
+/// assert_eq!(folded_levenshtein("Müller".as_bytes(), "Möller".as_bytes()), 1);
+/// assert_eq!(folded_levenshtein("Müller".as_bytes(), b"Muller"), 1);
+/// ```
+pub fn folded_levenshtein(a: &[u8], b: &[u8]) -> usize {
+    let fold = |bytes: &[u8]| fold_lowercase(&String::from_utf8_lossy(bytes));
+    let (a, b) = (fold(a), fold(b));
+    // distances from the prefix of `a` to every prefix of `b`, one row at a time
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, &byte_a) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &byte_b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(byte_a != byte_b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{folded_levenshtein, NormalizedName};
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::{BTreeMap, HashSet};
+    use std::hash::{Hash, Hasher};
+
+    fn hash(name: &NormalizedName) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        name.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn variants_collide() {
+        let mut map = BTreeMap::new();
+        for (i, name) in ["Müller", "Mueller", "MÜLLER", "mueller"]
+            .iter()
+            .enumerate()
+        {
+            map.insert(NormalizedName::new(*name), i);
+        }
+        assert_eq!(map.len(), 1);
+        let (key, value) = map.iter().next().unwrap();
+        // the first inserted key is kept, the value is replaced
+        assert_eq!(key.as_str(), "Müller");
+        assert_eq!(*value, 3);
+
+        let a = NormalizedName::new("Strauß");
+        let b = NormalizedName::new("STRAUSS");
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        let set: HashSet<_> = vec![a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn phone_book_order() {
+        let mut map = BTreeMap::new();
+        for name in ["Muff", "Müller", "Mader", "Mädler", "Mueck", "Maye"].iter() {
+            map.insert(NormalizedName::from(*name), ());
+        }
+        let sorted: Vec<_> = map.keys().map(NormalizedName::as_str).collect();
+        assert_eq!(
+            sorted,
+            vec!["Mader", "Mädler", "Maye", "Mueck", "Müller", "Muff"]
+        );
+        assert_ne!(NormalizedName::new("Müller"), NormalizedName::new("Muller"));
+    }
+
+    #[test]
+    fn levenshtein() {
+        let distance = |a: &str, b: &str| folded_levenshtein(a.as_bytes(), b.as_bytes());
+        assert_eq!(distance("Müller", "Mueller"), 0);
+        assert_eq!(distance("Strauß", "STRAUSS"), 0);
+        assert_eq!(distance("Müller", "Möller"), 1);
+        assert_eq!(distance("Müller", "Miller"), 2);
+        assert_eq!(distance("Schröder", "Schroeter"), 1);
+        assert_eq!(distance("Jürgen", "Jörg"), 3);
+        assert_eq!(distance("Köhler", "Kohler"), 1);
+        assert_eq!(distance("", "Öl"), 3);
+        assert_eq!(distance("Öl", ""), 3);
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("Élodie", "élodie"), 0);
+        assert_eq!(NormalizedName::new("Élodie"), NormalizedName::new("élodie"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Normalization of decomposed umlauts and whitespace.
+use crate::fold::FoldedBytes;
+
+/// UTF-8 encoding of U+0308 COMBINING DIAERESIS.
+pub(crate) const COMBINING_DIAERESIS: [u8; 2] = [0xcc, 0x88];
+
+/// Returns the precomposed umlaut for a base vowel followed by a combining diaeresis.
+fn composed_umlaut(base: u8) -> Option<&'static [u8]> {
+    match base {
+        b'a' => Some("ä".as_bytes()),
+        b'o' => Some("ö".as_bytes()),
+        b'u' => Some("ü".as_bytes()),
+        b'A' => Some("Ä".as_bytes()),
+        b'O' => Some("Ö".as_bytes()),
+        b'U' => Some("Ü".as_bytes()),
+        _ => None,
+    }
+}
+
+/// Composes decomposed umlauts (NFD) into precomposed ones (NFC).
+///
+/// Only 'a', 'o' and 'u' in both cases followed by U+0308 are composed,
+/// everything else is copied unchanged.
+pub(crate) fn compose_umlauts(bytes: &[u8]) -> Vec<u8> {
+    let mut composed = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (composed_umlaut(bytes[i]), bytes.get(i + 1..i + 3)) {
+            (Some(umlaut), Some(mark)) if mark == COMBINING_DIAERESIS => {
+                composed.extend_from_slice(umlaut);
+                i += 3;
+            }
+            _ => {
+                composed.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    composed
+}
+
+/// Removes the combining diaeresis of decomposed umlauts (NFD) in place.
+///
+/// See [`UmlautsInplaceExt::strip_diaeresis_nfd_in_place`](crate::UmlautsInplaceExt::strip_diaeresis_nfd_in_place).
+pub(crate) fn strip_diaeresis_in_place(bytes: &mut [u8]) -> usize {
+    let mut written = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let base = bytes[i];
+        bytes[written] = base;
+        written += 1;
+        i += match bytes.get(i + 1..i + 3) {
+            Some(mark) if mark == COMBINING_DIAERESIS && composed_umlaut(base).is_some() => 3,
+            _ => 1,
+        };
+    }
+    for byte in &mut bytes[written..] {
+        *byte = 0;
+    }
+    written
+}
+
+/// Appends `bytes` to `out` with runs of ASCII whitespace replaced by a single space
+/// and leading and trailing whitespace removed.
+pub(crate) fn collapse_whitespace(bytes: &[u8], out: &mut Vec<u8>) {
+    let start = out.len();
+    for word in bytes
+        .split(|byte| byte.is_ascii_whitespace())
+        .filter(|word| !word.is_empty())
+    {
+        if out.len() > start {
+            out.push(b' ');
+        }
+        out.extend_from_slice(word);
+    }
+}
+
+/// Computes the canonical form of a german text.
+///
+/// See [`UmlautsOwned::canonical_german`](crate::UmlautsOwned::canonical_german).
+pub(crate) fn canonical_german(bytes: &[u8]) -> Box<str> {
+    let lower = String::from_utf8_lossy(&compose_umlauts(bytes)).to_lowercase();
+    let mut canonical = Vec::with_capacity(lower.len());
+    collapse_whitespace(lower.as_bytes(), &mut canonical);
+    String::from_utf8_lossy(&canonical).into()
+}
+
+/// Folds `bytes` according to DIN 5007-2, lowercases ASCII and collapses ASCII whitespace.
+///
+/// Calls `emit` with every resulting byte. Runs of ASCII whitespace are emitted
+/// as a single space, leading and trailing whitespace is removed.
+pub(crate) fn fold_collapsed<F: FnMut(u8)>(bytes: &[u8], mut emit: F) {
+    let mut started = false;
+    let mut pending_space = false;
+    for byte in FoldedBytes::new(bytes) {
+        if byte.is_ascii_whitespace() {
+            pending_space = started;
+            continue;
+        }
+        if pending_space {
+            emit(b' ');
+            pending_space = false;
+        }
+        emit(byte.to_ascii_lowercase());
+        started = true;
+    }
+}
+
+/// Offset basis of the 64 bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Prime of the 64 bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the 64 bit FNV-1a hash of the folded, lowercased and whitespace collapsed text.
+///
+/// See [`UmlautsOwned::umlaut_fingerprint`](crate::UmlautsOwned::umlaut_fingerprint).
+pub(crate) fn fingerprint(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    fold_collapsed(&compose_umlauts(bytes), |byte| {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    });
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        canonical_german, collapse_whitespace, compose_umlauts, fingerprint, fold_collapsed,
+        strip_diaeresis_in_place,
+    };
+
+    #[test]
+    fn compose() {
+        assert_eq!(
+            compose_umlauts("Mu\u{308}ller O\u{308}l".as_bytes()),
+            "Müller Öl".as_bytes()
+        );
+        assert_eq!(
+            compose_umlauts("n\u{308}".as_bytes()),
+            "n\u{308}".as_bytes()
+        );
+        assert_eq!(compose_umlauts("äu".as_bytes()), "äu".as_bytes());
+        assert_eq!(compose_umlauts(&[b'a', 0xcc]), [b'a', 0xcc]);
+    }
+
+    #[test]
+    fn strip_diaeresis() {
+        let mut text = "Mu\u{308}ller O\u{308}l".as_bytes().to_vec();
+        assert_eq!(strip_diaeresis_in_place(&mut text), 9);
+        assert_eq!(&text[..9], b"Muller Ol");
+        assert_eq!(&text[9..], [0; 4]);
+
+        let mut text = "n\u{308}\u{308}ü".as_bytes().to_vec();
+        assert_eq!(strip_diaeresis_in_place(&mut text), text.len());
+        assert_eq!(text, "n\u{308}\u{308}ü".as_bytes());
+        let mut text = [b'o', 0xcc];
+        assert_eq!(strip_diaeresis_in_place(&mut text), 2);
+        assert_eq!(strip_diaeresis_in_place(&mut []), 0);
+    }
+
+    #[test]
+    fn collapse() {
+        let mut out = Vec::new();
+        collapse_whitespace(b" \t a  b\r\nc ", &mut out);
+        assert_eq!(out, b"a b c");
+        let mut out = Vec::new();
+        collapse_whitespace(b"   ", &mut out);
+        assert_eq!(out, b"");
+    }
+
+    #[test]
+    fn canonical() {
+        assert_eq!(
+            canonical_german(" Müller  ".as_bytes()),
+            canonical_german("müller".as_bytes())
+        );
+        assert_eq!(&*canonical_german("MU\u{308}LLER".as_bytes()), "müller");
+        assert_eq!(
+            &*canonical_german("  GROẞE   Straße ".as_bytes()),
+            "große straße"
+        );
+        assert_eq!(
+            canonical_german("Élodie Ørsted".as_bytes()),
+            canonical_german("élodie ørsted".as_bytes())
+        );
+        assert_eq!(&*canonical_german("ÉLODIE".as_bytes()), "élodie");
+        // only umlauts are composed
+        assert_eq!(&*canonical_german("cafe\u{301}".as_bytes()), "cafe\u{301}");
+        assert_ne!(
+            canonical_german("cafe\u{301}".as_bytes()),
+            canonical_german("café".as_bytes())
+        );
+    }
+
+    #[test]
+    fn fold_collapse() {
+        let fold = |text: &str| {
+            let mut out = Vec::new();
+            fold_collapsed(text.as_bytes(), |byte| out.push(byte));
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(fold("  Grüße \t aus\r\nKÖLN  "), "gruesse aus koeln");
+        assert_eq!(fold("GROẞE"), "grosse");
+        // only ASCII whitespace is collapsed
+        assert_eq!(fold("a\u{a0} b"), "a\u{a0} b");
+        assert_eq!(fold(" \n "), "");
+    }
+
+    #[test]
+    fn fingerprint_stable() {
+        // FNV-1a of "hans mueller", fixed to detect accidental changes
+        let expected = 0x097e_0099_4bfa_394b;
+        for name in &[
+            "Hans Müller",
+            "hans mueller",
+            "  HANS   MÜLLER\t",
+            "Hans Mu\u{308}ller",
+            "HANS\nMUELLER",
+        ] {
+            assert_eq!(fingerprint(name.as_bytes()), expected, "{:?}", name);
+        }
+        assert_ne!(fingerprint(b"Hans Muller"), expected);
+        assert_ne!(fingerprint(b"HansMueller"), expected);
+        assert_eq!(fingerprint(b" "), fingerprint(b""));
+        assert_eq!(fingerprint(b""), 0xcbf2_9ce4_8422_2325);
+    }
+}
@@ -0,0 +1,112 @@
+//! Conversion of german number formatting to the english one.
+
+/// Rewrites numbers like "1.234,56" to "1234.56".
+///
+/// See [`UmlautsOwned::normalize_german_numbers`](crate::UmlautsOwned::normalize_german_numbers).
+pub(crate) fn normalize_german_numbers(bytes: &[u8]) -> Vec<u8> {
+    let mut normalized = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let starts_number = bytes[i].is_ascii_digit()
+            && !i
+                .checked_sub(1)
+                .is_some_and(|prev| is_number_neighbour(bytes[prev]));
+        if !starts_number {
+            normalized.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        let end = number_end(bytes, i);
+        let number = &bytes[i..end];
+        if is_german_number(number) {
+            for &byte in number {
+                match byte {
+                    b'.' => {}
+                    b',' => normalized.push(b'.'),
+                    digit => normalized.push(digit),
+                }
+            }
+        } else {
+            normalized.extend_from_slice(number);
+        }
+        i = end;
+    }
+    normalized
+}
+
+/// Checks if `byte` directly before a digit makes it part of a word or another number.
+fn is_number_neighbour(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'.' || byte == b','
+}
+
+/// Returns the end of the digits, '.' and ',' starting at `start`.
+///
+/// A separator is only included if a digit follows, so "3," at the end of a clause ends at the '3'.
+fn number_end(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    while end < bytes.len() {
+        match bytes[end] {
+            b'0'..=b'9' => end += 1,
+            b'.' | b',' if bytes.get(end + 1).is_some_and(u8::is_ascii_digit) => end += 1,
+            _ => break,
+        }
+    }
+    end
+}
+
+/// Checks if `number` is grouped with '.' by thousands and/or has a decimal ','.
+fn is_german_number(number: &[u8]) -> bool {
+    let mut parts = number.split(|&byte| byte == b',');
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+    if parts.next().is_some() || fraction.is_some_and(|fraction| fraction.contains(&b'.')) {
+        return false;
+    }
+    let mut groups = integer.split(|&byte| byte == b'.');
+    let first = groups.next().unwrap_or_default();
+    let grouped = integer.contains(&b'.');
+    if !grouped {
+        return fraction.is_some();
+    }
+    (1..=3).contains(&first.len()) && first[0] != b'0' && groups.all(|group| group.len() == 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize_german_numbers;
+
+    fn normalize(text: &str) -> String {
+        String::from_utf8(normalize_german_numbers(text.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(normalize("1.234,56"), "1234.56");
+        assert_eq!(normalize("Preis: 1.234.567,89 €"), "Preis: 1234567.89 €");
+        assert_eq!(normalize("3,5 kg und 12 Stück"), "3.5 kg und 12 Stück");
+        assert_eq!(normalize("1.000 Einwohner"), "1000 Einwohner");
+        assert_eq!(normalize("-0,5°C"), "-0.5°C");
+        assert_eq!(normalize("Es kostet 3,"), "Es kostet 3,");
+        assert_eq!(normalize("Um 1.234."), "Um 1234.");
+    }
+
+    #[test]
+    fn not_numbers() {
+        for text in [
+            "am 12.03.2024",
+            "um 12.30 Uhr",
+            "Version 1.2.3",
+            "1,2,3",
+            "0.500",
+            "12345.678",
+            "1.234,5.6",
+            "A4,5",
+            "Seite 3, 4 und 5",
+            "",
+        ]
+        .iter()
+        {
+            assert_eq!(normalize(text), *text);
+        }
+    }
+}
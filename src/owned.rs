@@ -0,0 +1,2356 @@
+use crate::array;
+use crate::case;
+use crate::filename::FilenameStyle;
+use crate::fold::{
+    self, din_5007_2_at, fold_char_bytes, fold_din_5007_2, fold_din_5007_2_capped,
+    is_fold_reversible, next_umlaut, FoldDiff, FoldTrace, FoldedBytes, FoldedTokens,
+};
+use crate::mojibake;
+use crate::normalize;
+#[cfg(feature = "locale_numbers")]
+use crate::numbers;
+use crate::phonetic;
+use crate::reverse;
+use crate::stats;
+use crate::typeable;
+use crate::utf8::decode_last_char;
+#[cfg(feature = "segmentation")]
+use crate::GermanGraphemes;
+use crate::{
+    CapacityError, NonAsciiError, SharpSPolicy, Transliterator, TypeConvention, Umlaut, UmlautSet,
+    UmlautStats,
+};
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+use std::io::{self, Write};
+use std::ops::Range;
+
+/// Allocating string processing functions.
+///
+/// `UmlautsOwned` adds string processing functions for the german "Umlauts"
+/// 'ä', 'ö', 'ü', 'ß' and their uppercase variants, which return newly allocated
+/// data instead of manipulating the input in place.
+/// In contrast to [`UmlautsInplaceExt`](crate::UmlautsInplaceExt) these functions
+/// are not limited by the size of their input.
+pub trait UmlautsOwned {
+    /// Computes the Kölner Phonetik code of a text.
+    ///
+    /// The Kölner Phonetik is the german equivalent of Soundex.
+    /// Umlauts are normalized before applying the rules, so that different spellings of
+    /// the same name get the same code.
+    /// All characters besides ASCII letters and umlauts are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Müller-Lüdenscheidt".koelner_phonetik(), "65752682");
+    /// assert_eq!("Müller".koelner_phonetik(), "Mueller".koelner_phonetik());
+    /// assert_eq!("Müller".koelner_phonetik(), "Miller".koelner_phonetik());
+    /// ```
+    fn koelner_phonetik(&self) -> String;
+
+    /// Iterates over the umlauts and their replacements according to DIN 5007-2.
+    ///
+    /// Yields the byte range of every umlaut in the original text together with the
+    /// bytes it would be replaced with, e.g. for rendering a before/after preview.
+    /// All other characters are left out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let diff: Vec<_> = "Grüße".fold_diff().collect();
+    /// assert_eq!(diff, vec![(2..4, &b"ue"[..]), (4..6, &b"ss"[..])]);
+    /// ```
+    fn fold_diff(&self) -> FoldDiff<'_>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and pads or truncates to exactly `width` bytes.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`make_utf8_umlauts_to_ascii`](crate::UmlautsInplaceExt::make_utf8_umlauts_to_ascii),
+    /// including the uppercase ß ('ẞ' -> 'SS').
+    /// The folded text is truncated without cutting a replacement or a
+    /// multi-byte character in half, the remaining bytes are filled up with `pad`.
+    /// A non-ASCII `pad` makes the output non-ASCII.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".fold_to_fixed(4, b' '), b"Grue");
+    /// assert_eq!("Grüße".fold_to_fixed(5, b' '), b"Grue ");
+    /// assert_eq!("Öl".fold_to_fixed(6, b'_'), b"Oel___");
+    /// ```
+    fn fold_to_fixed(&self, width: usize, pad: u8) -> Vec<u8>;
+
+    /// Uppercases the first alphabetic character and leaves the rest untouched.
+    ///
+    /// In contrast to uppercasing the first byte this also works for leading umlauts:
+    /// - 'ä' -> 'Ä'
+    /// - 'ö' -> 'Ö'
+    /// - 'ü' -> 'Ü'
+    /// - 'ß' -> 'Ss'
+    ///
+    /// Leading characters that are not alphabetic, like quotes or digits, are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("übermut tut selten gut".capitalize_first_umlaut_aware(),
+    ///     "Übermut tut selten gut".as_bytes());
+    /// ```
+    fn capitalize_first_umlaut_aware(&self) -> Vec<u8>;
+
+    /// Checks if folding to ASCII according to DIN 5007-2 can be reversed unambiguously.
+    ///
+    /// Reversing reads the folded text from left to right and replaces every
+    /// 'ae', 'oe', 'ue' and 'ss' (in any case) with the matching umlaut.
+    /// This returns `true` exactly if every such sequence found this way originates
+    /// from an umlaut, so that reversing yields the original text.
+    ///
+    /// Texts that already contain a literal 'ae', 'oe', 'ue' or 'ss' are not reversible,
+    /// neither is an 's' directly in front of a 'ß', as "sß" and "ßs" both fold to "sss".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert!("Müller".is_ascii_fold_reversible());
+    /// assert!("Maßstab".is_ascii_fold_reversible());
+    /// // "Mueller" would be read as "Müller"
+    /// assert!(!"Mueller".is_ascii_fold_reversible());
+    /// // "Messgerät" folds to "Messgeraet", which would be read as "Meßgerät"
+    /// assert!(!"Messgerät".is_ascii_fold_reversible());
+    /// ```
+    fn is_ascii_fold_reversible(&self) -> bool;
+
+    /// Counts the umlauts "äöüÄÖÜßẞ".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Müller grüßt Öl".count_umlauts(), 4);
+    /// ```
+    fn count_umlauts(&self) -> usize;
+
+    /// Returns the umlauts "äöüÄÖÜßẞ" in the order they appear.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::Umlaut;
+    ///
+    /// assert_eq!("Müller grüßt Öl".umlaut_sequence(), vec![
+    ///     Umlaut::LowercaseU,
+    ///     Umlaut::LowercaseU,
+    ///     Umlaut::LowercaseSharpS,
+    ///     Umlaut::UppercaseO,
+    /// ]);
+    /// ```
+    fn umlaut_sequence(&self) -> Vec<Umlaut>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... into an existing `String`.
+    ///
+    /// Clears `out` and appends the text folded according to DIN 5007-2, including the
+    /// uppercase ß ('ẞ' -> 'SS'). The capacity of `out` is reused, which avoids an allocation
+    /// per call when folding many texts.
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut out = String::new();
+    /// "Öl".fold_ascii_into(&mut out);
+    /// assert_eq!(out, "Oel");
+    /// "Grüße".fold_ascii_into(&mut out);
+    /// assert_eq!(out, "Gruesse");
+    /// ```
+    fn fold_ascii_into(&self, out: &mut String);
+
+    /// Computes a canonical form of a text, which keeps the umlauts.
+    ///
+    /// The text is lowercased like by `str::to_lowercase` ('ẞ' -> 'ß', 'É' -> 'é'), so it
+    /// matches the lowercasing of [`NormalizedName`](crate::NormalizedName).
+    /// Decomposed umlauts like "u\u{308}" are composed to 'ü' and runs of ASCII whitespace
+    /// are collapsed to a single space and trimmed.
+    /// This is not a full NFC normalization: only the german umlauts are composed, other
+    /// decomposed characters like "e\u{301}" are kept, so "cafe\u{301}" and "café" differ.
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    /// Useful as a compact key for deduplicating names without losing the umlauts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!(" Müller  ".canonical_german(), "müller".canonical_german());
+    /// assert_eq!(&*"Mu\u{308}ller".canonical_german(), "müller");
+    /// ```
+    fn canonical_german(&self) -> Box<str>;
+
+    /// Converts Umlauts to ASCII with a replacement depending on the position in a word.
+    ///
+    /// `f` is called for every umlaut with `true` if it starts a word and returns the
+    /// replacement. An umlaut starts a word if it is the first character or does not follow
+    /// an alphanumeric character.
+    /// [`Umlaut::din_5007_2`] ignoring the position is the default according to DIN 5007-2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::Umlaut;
+    ///
+    /// let text = "Öl und Äpfel für Übermut";
+    /// assert_eq!(text.fold_ascii_word_aware(|_, umlaut| umlaut.din_5007_2()),
+    ///     b"Oel und Aepfel fuer Uebermut");
+    /// let folded = text.fold_ascii_word_aware(|at_word_start, umlaut| match umlaut {
+    ///     Umlaut::UppercaseA if at_word_start => &b"AE"[..],
+    ///     Umlaut::UppercaseO if at_word_start => b"OE",
+    ///     Umlaut::UppercaseU if at_word_start => b"UE",
+    ///     umlaut => umlaut.din_5007_2(),
+    /// });
+    /// assert_eq!(folded, b"OEl und AEpfel fuer UEbermut");
+    /// ```
+    fn fold_ascii_word_aware<F, R>(&self, f: F) -> Vec<u8>
+    where
+        F: Fn(bool, Umlaut) -> R,
+        R: AsRef<[u8]>;
+
+    /// Converts text folded according to DIN 5007-2 to the folding of DIN 5007-1.
+    ///
+    /// DIN 5007-2 folds 'ä' to 'ae', DIN 5007-1 folds it to 'a'.
+    /// As a folded 'ae', 'oe' or 'ue' can't be told apart from a literal one,
+    /// this is best-effort: a pair is left unchanged if it follows another vowel ("Bauer",
+    /// "neue"), for 'ue' after 'q' ("Quelle") and in words containing one of
+    /// [`LITERAL_VOWEL_PAIR_WORDS`](crate::LITERAL_VOWEL_PAIR_WORDS) ("Michael").
+    /// Everything else is collapsed, so unknown words with a literal pair are changed.
+    /// 'ss' stays 'ss', as DIN 5007-1 folds 'ß' to 'ss' as well.
+    ///
+    /// Unlike the DIN 5007-2 folds, this is not idempotent: a collapsed pair can form
+    /// a new pair with the following letter, so "Maeer" becomes "Maer" and then "Mar".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Mueller, Bauer und Michael".din2_to_din1_ascii(), b"Muller, Bauer und Michael");
+    /// ```
+    fn din2_to_din1_ascii(&self) -> Vec<u8>;
+
+    /// Computes the width of a text on a monospace terminal.
+    ///
+    /// Every umlaut and every printable ASCII character is one column wide,
+    /// ASCII control characters and the combining diaeresis (U+0308) of decomposed
+    /// umlauts have no width.
+    /// Other characters are counted as one column as well, wide characters
+    /// like those of east asian scripts are not taken into account.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".german_display_width(), 5);
+    /// assert_eq!("Gruesse".german_display_width(), 7);
+    /// ```
+    fn german_display_width(&self) -> usize;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... except inside protected substrings.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// but every occurrence of one of the `protected` substrings is copied unchanged.
+    /// If several protected substrings start at the same position, the longest one wins.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let text = "Die Müller-GmbH grüßt Herrn Müller";
+    /// assert_eq!(text.fold_ascii_protecting(&["Müller-GmbH".as_bytes()]),
+    ///     "Die Müller-GmbH gruesst Herrn Mueller".as_bytes());
+    /// ```
+    fn fold_ascii_protecting(&self, protected: &[&[u8]]) -> Vec<u8>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and rejects all other non-ASCII characters.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS').
+    /// Characters that have no german or ASCII folding, like 'é' or emoji, are not passed
+    /// through. Instead, the byte offset and the character of each of them is returned.
+    /// Invalid UTF-8 is reported as U+FFFD for every byte that does not start a valid character.
+    ///
+    /// With the `symbols` feature, '°' and 'µ' are replaced by "deg" and 'u' instead,
+    /// german quotation marks like '„' and '“' by straight ASCII quotes,
+    /// and fullwidth latin letters like 'Ａ' by their ASCII equivalent.
+    ///
+    /// With the `latin` feature, the latin letters folded by
+    /// [`Transliterator::fold_ascii`](crate::Transliterator::fold_ascii), like 'æ' and 'ÿ',
+    /// are accepted as well.
+    ///
+    /// With the `log` feature, every rejected character is logged as a warning.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".fold_ascii_strict(), Ok(b"Gruesse".to_vec()));
+    /// assert_eq!("Café Müller".fold_ascii_strict(), Err(vec![(3, 'é')]));
+    /// ```
+    fn fold_ascii_strict(&self) -> Result<Vec<u8>, Vec<(usize, char)>>;
+
+    /// Creates a lookup key by folding, lowercasing and removing all but ASCII letters and digits.
+    ///
+    /// Maps umlauts according to DIN 5007-2, lowercases ASCII and drops every byte
+    /// that is not in `[a-z0-9]` after folding, in a single pass.
+    /// Use [`fold_key_retaining`](UmlautsOwned::fold_key_retaining) to keep other bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Müller & Söhne (2024)".fold_alnum_key(), b"muellersoehne2024");
+    /// ```
+    fn fold_alnum_key(&self) -> Vec<u8>;
+
+    /// Creates a lookup key by folding, lowercasing and keeping only bytes matching `keep`.
+    ///
+    /// Like [`fold_alnum_key`](UmlautsOwned::fold_alnum_key), but `keep` is called with every
+    /// folded and lowercased byte and decides if it is retained.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let key = "Müller & Söhne".fold_key_retaining(|byte| byte.is_ascii_lowercase() || byte == b' ');
+    /// assert_eq!(key, b"mueller  soehne");
+    /// ```
+    fn fold_key_retaining<F>(&self, keep: F) -> Vec<u8>
+    where
+        F: FnMut(u8) -> bool;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... into a fixed size buffer.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// and writes the result to the start of `out` without allocating.
+    /// Returns the number of written bytes, or a [`CapacityError`] if the folded text
+    /// does not fit. The content of `out` is unspecified in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut buf = [0; 16];
+    /// let len = "Grüße".fold_ascii_into_array(&mut buf).unwrap();
+    /// assert_eq!(&buf[..len], b"Gruesse");
+    ///
+    /// let mut small = [0; 4];
+    /// assert!("Grüße".fold_ascii_into_array(&mut small).is_err());
+    /// ```
+    fn fold_ascii_into_array<const N: usize>(
+        &self,
+        out: &mut [u8; N],
+    ) -> Result<usize, CapacityError>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and writes the result to `writer`.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS').
+    /// The folded text is written in parts between the umlauts,
+    /// without building an intermediate buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error returned by `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut out = Vec::new();
+    /// "Grüße".fold_ascii_to_writer(&mut out).unwrap();
+    /// assert_eq!(out, b"Gruesse");
+    /// ```
+    fn fold_ascii_to_writer<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Checks if [`make_utf8_umlauts_to_ascii`] folds the text like the allocating functions.
+    ///
+    /// The in place fold can't expand, so it leaves the uppercase ß ('ẞ') unchanged
+    /// and always writes 'ß' as 'ss'. This returns `false` if the text contains an 'ẞ',
+    /// or an 'ß' that [`Transliterator::fold_ascii`] expands to "SS" in an uppercase word,
+    /// like in "GROß".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert!("Grüße aus Köln".in_place_fold_is_lossless());
+    /// assert!(!"GROẞE".in_place_fold_is_lossless());
+    /// assert!(!"GROß".in_place_fold_is_lossless());
+    /// ```
+    ///
+    /// [`make_utf8_umlauts_to_ascii`]: crate::UmlautsInplaceExt::make_utf8_umlauts_to_ascii
+    /// [`Transliterator::fold_ascii`]: crate::Transliterator::fold_ascii
+    fn in_place_fold_is_lossless(&self) -> bool;
+
+    /// Returns the number of leading ASCII bytes before the first umlaut or other non-ASCII byte.
+    ///
+    /// The prefix is unchanged by all folds and can be copied verbatim.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Viele Grüße".ascii_prefix_len(), 8);
+    /// assert_eq!("Hallo".ascii_prefix_len(), 5);
+    /// ```
+    fn ascii_prefix_len(&self) -> usize;
+
+    /// Replaces umlauts with their base vowel followed by `filler`, keeping the byte length.
+    ///
+    /// - 'ä' -> 'a' + `filler`
+    /// - 'ö' -> 'o' + `filler`
+    /// - 'ü' -> 'u' + `filler`
+    /// - 'Ä', 'Ö' and 'Ü' likewise
+    ///
+    /// 'ß' and 'ẞ' have no base vowel and are left unchanged, as are all other bytes.
+    /// Every byte offset in the result refers to the same character as in the input,
+    /// which keeps both renderings aligned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Bär und Öl".base_vowel_aligned(b' '), b"Ba r und O l");
+    /// ```
+    fn base_vowel_aligned(&self, filler: u8) -> Vec<u8>;
+
+    /// Returns an iterator over the whitespace separated tokens, and their folded forms.
+    ///
+    /// Each token is yielded with its byte range in the original text and
+    /// its form folded according to DIN 5007-2, e.g. for indexing folded terms
+    /// while highlighting the original spelling.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut tokens = "Grüße aus Köln".folded_tokens();
+    /// assert_eq!(tokens.next(), Some((0..7, b"Gruesse".to_vec())));
+    /// assert_eq!(tokens.next(), Some((8..11, b"aus".to_vec())));
+    /// assert_eq!(tokens.next(), Some((12..17, b"Koeln".to_vec())));
+    /// assert_eq!(tokens.next(), None);
+    /// ```
+    fn folded_tokens(&self) -> FoldedTokens<'_>;
+
+    /// Returns an iterator over the grapheme clusters of the text.
+    ///
+    /// A base vowel and a following combining diaeresis (NFD) are yielded together,
+    /// so they can be folded as a whole. Invalid UTF-8 bytes are yielded as separate items.
+    ///
+    /// Requires the `segmentation` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let graphemes: Vec<&[u8]> = "Ko\u{308}ln".german_graphemes().collect();
+    /// assert_eq!(graphemes[1], "o\u{308}".as_bytes());
+    /// assert_eq!(graphemes.len(), 4);
+    /// ```
+    #[cfg(feature = "segmentation")]
+    fn german_graphemes(&self) -> GermanGraphemes<'_>;
+
+    /// Checks that the text contains no byte above 0x7f, as the result of a fold should.
+    ///
+    /// Intended as a post-condition for folded text, e.g. to catch missing mappings.
+    /// Returns the position of the first non-ASCII byte otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let folded = "GROẞE Grüße".fold_ascii_strict().unwrap();
+    /// assert!(folded.ensure_folded_ascii().is_ok());
+    /// assert_eq!("Café".ensure_folded_ascii().unwrap_err().position(), 3);
+    /// ```
+    fn ensure_folded_ascii(&self) -> Result<(), NonAsciiError>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and reverses the order of the characters.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// and returns the characters in reverse order, e.g. for building suffix indexes.
+    /// Each expansion is one character and keeps its order, so "ä" becomes "ae", not "ea".
+    /// Other multi-byte characters are kept intact, invalid bytes are reversed one by one.
+    /// Reversing the result again restores the folded text, so this is not idempotent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".fold_ascii_reversed(), b"essuerG");
+    /// assert_eq!("Köln".fold_ascii_reversed(), b"nloeK");
+    /// ```
+    fn fold_ascii_reversed(&self) -> Vec<u8>;
+
+    /// Computes a fingerprint that is equal for different spellings of the same text.
+    ///
+    /// The text is folded according to DIN 5007-2 after composing decomposed umlauts,
+    /// lowercased, and runs of whitespace are collapsed to a single space with leading and
+    /// trailing whitespace removed. The result is hashed with the 64 bit FNV-1a hash, which
+    /// is stable across runs, platforms and versions of this crate, so fingerprints can be
+    /// stored, e.g. for deduplicating names in a database.
+    ///
+    /// The fingerprint is an aid for deduplication, not a cryptographic hash.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Hans Müller".umlaut_fingerprint(), " HANS  MUELLER".umlaut_fingerprint());
+    /// assert_ne!("Hans Müller".umlaut_fingerprint(), "Hans Muller".umlaut_fingerprint());
+    /// ```
+    fn umlaut_fingerprint(&self) -> u64;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... lowercases and collapses whitespace.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// lowercases ASCII letters and replaces every run of ASCII whitespace with a single
+    /// space, removing leading and trailing whitespace, in a single pass.
+    /// Only ASCII whitespace is collapsed, so other spaces like the no-break space U+00A0
+    /// are kept, like by [`str::split_ascii_whitespace`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("  Viele   Grüße\n aus KÖLN ".fold_and_collapse_ws(), b"viele gruesse aus koeln");
+    /// ```
+    fn fold_and_collapse_ws(&self) -> Vec<u8>;
+
+    /// Checks if the text contains no umlaut or 'ß', so folding it again is a no-op.
+    ///
+    /// If this returns `true`, every DIN 5007-2 fold returns the text unchanged,
+    /// e.g. for text that has been folded before.
+    /// Other non-ASCII characters are allowed, use [`ensure_folded_ascii`](UmlautsOwned::ensure_folded_ascii)
+    /// to also reject them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert!(!"Grüße".is_already_ascii_folded());
+    /// assert!("Gruesse".is_already_ascii_folded());
+    /// assert!("Gruesse, café".is_already_ascii_folded());
+    /// ```
+    fn is_already_ascii_folded(&self) -> bool;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and reports if the text changed.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// and returns the folded bytes together with `true` if any umlaut has been replaced,
+    /// in a single pass. If the flag is `false`, the folded bytes equal the input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".fold_ascii_reporting(), (b"Gruesse".to_vec(), true));
+    /// assert_eq!("Gruesse".fold_ascii_reporting(), (b"Gruesse".to_vec(), false));
+    /// ```
+    fn fold_ascii_reporting(&self) -> (Vec<u8>, bool);
+
+    /// Lowercases alphabetic ASCII chars, UTF-8 umlauts and the uppercase ß.
+    ///
+    /// Like [`make_utf8_umlauts_lowercase`](crate::UmlautsInplaceExt::make_utf8_umlauts_lowercase),
+    /// but also lowercases 'ẞ' to 'ß', which the in place version can't do, as 'ß' is one byte shorter:
+    /// - 'Ä' -> 'ä'
+    /// - 'Ö' -> 'ö'
+    /// - 'Ü' -> 'ü'
+    /// - 'ẞ' -> 'ß'
+    ///
+    /// All other non-ASCII characters are left unchanged, use [`str::to_lowercase`]
+    /// to lowercase them as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("GRÜẞE ÖL".to_german_lowercase(), "grüße öl".as_bytes());
+    /// ```
+    fn to_german_lowercase(&self) -> Vec<u8>;
+
+    /// Splits CamelCase compound words before every internal capital.
+    ///
+    /// A new part starts at every uppercase character that directly follows a lowercase one,
+    /// so capital umlauts like in "HeizÖl" are boundaries as well.
+    /// Other characters, e.g. '-' or digits, never start a new part.
+    /// Invalid UTF-8 bytes are kept and neither lowercase nor uppercase.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let parts = "MüllerStraßeHeizÖl".split_camel_umlaut_aware();
+    /// assert_eq!(parts, vec!["Müller".as_bytes(), "Straße".as_bytes(), b"Heiz", "Öl".as_bytes()]);
+    /// ```
+    fn split_camel_umlaut_aware(&self) -> Vec<&[u8]>;
+
+    /// Replaces umlauts by the ASCII sequence used to type them in `convention`.
+    ///
+    /// Meant as a fallback for input methods, see [`TypeConvention`] for the available conventions.
+    /// All other bytes are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::TypeConvention;
+    ///
+    /// assert_eq!("Grüße".to_typeable_ascii(TypeConvention::DoubleQuotePrefix), b"Gr\"u\"se");
+    /// assert_eq!("Grüße".to_typeable_ascii(TypeConvention::DinAe), b"Gruesse");
+    /// ```
+    fn to_typeable_ascii(&self, convention: TypeConvention) -> Vec<u8>;
+
+    /// Writes every sharp s in the same form.
+    ///
+    /// Replaces 'ß' (U+00DF) and the capital 'ẞ' (U+1E9E) with 'ẞ' if `to_capital` is set,
+    /// and with 'ß' otherwise. All other bytes are left unchanged.
+    /// As 'ẞ' is one byte longer than 'ß', this can't be done in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Straße STRAẞE".unify_sharp_s(false), "Straße STRAßE".as_bytes());
+    /// assert_eq!("Straße STRAẞE".unify_sharp_s(true), "Straẞe STRAẞE".as_bytes());
+    /// ```
+    fn unify_sharp_s(&self, to_capital: bool) -> Vec<u8>;
+
+    /// Returns the byte offsets of umlauts that [`make_utf8_umlauts_to_ascii`] folds incorrectly.
+    ///
+    /// These are the uppercase ß ('ẞ'), which the in place fold leaves unchanged,
+    /// and every 'ß' that [`Transliterator::fold_ascii`] expands to "SS" in an uppercase word,
+    /// like in "GROß". The text is lossless as checked by
+    /// [`in_place_fold_is_lossless`](UmlautsOwned::in_place_fold_is_lossless) if this is empty.
+    /// Use the allocating functions like [`Transliterator::fold_ascii`] or
+    /// [`fold_ascii_reusing_capacity`] to fold text with such positions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("GROẞE Grüße, GROß".unsafe_inplace_positions(), vec![3, 20]);
+    /// assert!("Grüße".unsafe_inplace_positions().is_empty());
+    /// ```
+    ///
+    /// [`make_utf8_umlauts_to_ascii`]: crate::UmlautsInplaceExt::make_utf8_umlauts_to_ascii
+    /// [`Transliterator::fold_ascii`]: crate::Transliterator::fold_ascii
+    /// [`fold_ascii_reusing_capacity`]: crate::UmlautsVecExt::fold_ascii_reusing_capacity
+    fn unsafe_inplace_positions(&self) -> Vec<usize>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... keeping the case of all other letters.
+    ///
+    /// The case preserving allocating fold: maps umlauts according to DIN 5007-2,
+    /// including the uppercase ß ('ẞ' -> 'SS'), and never changes the case of an ASCII letter,
+    /// so proper nouns keep their capitalization. Unlike
+    /// [`make_utf8_umlauts_to_ascii`](crate::UmlautsInplaceExt::make_utf8_umlauts_to_ascii),
+    /// this also folds 'ẞ'.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Müller-Lüdenscheidt".fold_ascii_keep_case(), b"Mueller-Luedenscheidt");
+    /// assert_eq!("ÄRGER, GROẞ".fold_ascii_keep_case(), b"AeRGER, GROSS");
+    /// ```
+    fn fold_ascii_keep_case(&self) -> Vec<u8>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... into a `String`.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`fold_ascii_keep_case`](UmlautsOwned::fold_ascii_keep_case).
+    /// The result of a `str` is always valid UTF-8, invalid UTF-8 in a `[u8]`
+    /// is replaced with U+FFFD like by [`String::from_utf8_lossy`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße aus Köln".fold_ascii_string(), "Gruesse aus Koeln");
+    /// assert_eq!([b'a', 0xc3].fold_ascii_string(), "a\u{fffd}");
+    /// ```
+    fn fold_ascii_string(&self) -> String;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and counts them in the same pass.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`fold_ascii_keep_case`](UmlautsOwned::fold_ascii_keep_case) and returns the
+    /// number of occurrences of each umlaut alongside the folded bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::Umlaut;
+    ///
+    /// let (folded, stats) = "Straße, GROẞE Grüße".fold_with_stats();
+    /// assert_eq!(folded, b"Strasse, GROSSE Gruesse");
+    /// assert_eq!(stats.count(Umlaut::LowercaseSharpS), 2);
+    /// assert_eq!(stats.count(Umlaut::UppercaseSharpS), 1);
+    /// assert_eq!(stats.total(), 4);
+    /// ```
+    fn fold_with_stats(&self) -> (Vec<u8>, UmlautStats);
+
+    /// Rewrites german number formatting like "1.234,56" to "1234.56".
+    ///
+    /// Requires the `locale_numbers` feature.
+    ///
+    /// A number starts at a digit that does not follow a letter, digit, '.' or ','
+    /// and consists of digits joined by single '.' or ','. It is rewritten only if it
+    /// - has a decimal ',' followed by digits, like "3,5" -> "3.5", and/or
+    /// - groups its integer part with '.' by thousands, with one to three digits
+    ///   not starting with '0' before the first '.', like "1.000" -> "1000".
+    ///
+    /// All other text is left unchanged, including dates like "12.03.2024", times like "12.30",
+    /// version numbers and numbers with more than one ','.
+    /// The heuristic can't tell a german "1.234" from an english decimal number,
+    /// or a list like "1,2" written without spaces from a decimal number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!(
+    ///     "Am 12.03.2024 für 1.234,56 €".normalize_german_numbers(),
+    ///     "Am 12.03.2024 für 1234.56 €".as_bytes()
+    /// );
+    /// ```
+    #[cfg(feature = "locale_numbers")]
+    fn normalize_german_numbers(&self) -> Vec<u8>;
+
+    /// Returns an iterator over the characters and their DIN 5007-2 replacements.
+    ///
+    /// Yields the byte offset, the character and the folded bytes of every character,
+    /// see [`FoldTrace`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let trace: Vec<_> = "Öl!".fold_trace().collect();
+    /// assert_eq!(trace, vec![(0, 'Ö', &b"Oe"[..]), (2, 'l', &b"l"[..]), (3, '!', &b"!"[..])]);
+    /// ```
+    fn fold_trace(&self) -> FoldTrace<'_>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... including decomposed umlauts (NFD).
+    ///
+    /// Like [`fold_ascii_keep_case`](UmlautsOwned::fold_ascii_keep_case), but an 'a', 'o' or 'u'
+    /// in either case followed by a combining diaeresis (U+0308) is folded like the
+    /// precomposed umlaut as well. Only the diaeresis after these vowels is consumed,
+    /// other base letters and combining marks like the 'n' and tilde of a decomposed 'ñ'
+    /// are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Mu\u{308}ller, Grüße".fold_ascii_nfd(), b"Mueller, Gruesse");
+    /// assert_eq!("Pin\u{303}a".fold_ascii_nfd(), "Pin\u{303}a".as_bytes());
+    /// ```
+    fn fold_ascii_nfd(&self) -> Vec<u8>;
+
+    /// Restores umlauts that have been encoded as UTF-8 twice.
+    ///
+    /// Text encoded as UTF-8, read as Latin-1 or Windows-1252 and encoded as UTF-8 again
+    /// shows 'ü' as "Ã¼" and 'ß' as "ÃŸ". These sequences are replaced with the
+    /// umlauts "äöüÄÖÜßẞ" they came from. Double encoded characters beyond the german
+    /// umlauts, like "Ã©" for 'é', are left unchanged, as are all other bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("GrÃ¼ÃŸe aus KÃ¶ln".fix_double_encoded_umlauts(), "Grüße aus Köln".as_bytes());
+    /// ```
+    fn fix_double_encoded_umlauts(&self) -> Vec<u8>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... into a bump allocator.
+    ///
+    /// Requires the `bumpalo` feature.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`fold_ascii_keep_case`](UmlautsOwned::fold_ascii_keep_case), but allocates the
+    /// folded text in `bump` instead of the heap, so it is freed with the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use bumpalo::Bump;
+    /// use umlauts::prelude::*;
+    ///
+    /// let bump = Bump::new();
+    /// assert_eq!("Grüße".fold_ascii_in(&bump), b"Gruesse");
+    /// ```
+    #[cfg(feature = "bumpalo")]
+    fn fold_ascii_in<'b>(&self, bump: &'b Bump) -> &'b [u8];
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and replaces characters reserved in file names.
+    ///
+    /// Maps umlauts according to DIN 5007-2 and replaces the characters `<>:"/\|?*`,
+    /// which are not allowed in file names on Windows and FAT file systems, with '_'.
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    /// Use [`FilenameStyle`](crate::FilenameStyle) to configure the reserved characters
+    /// and the replacement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Größe/Übung?.txt".to_safe_filename(), "Groesse_Uebung_.txt");
+    /// ```
+    fn to_safe_filename(&self) -> String;
+
+    /// Replaces umlauts and 'ß' with HTML numeric character references.
+    ///
+    /// 'ä' becomes "&#228;", 'ẞ' becomes "&#7838;" and so on. Numeric references are used
+    /// instead of named entities, as there is no named entity for 'ẞ'.
+    /// Browsers still show the umlauts, while the text only contains ASCII if it contains
+    /// no other non-ASCII characters. All other bytes are copied unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".umlauts_to_html_numeric(), b"Gr&#252;&#223;e");
+    /// ```
+    fn umlauts_to_html_numeric(&self) -> Vec<u8>;
+
+    /// Returns the set of umlauts occurring in the text.
+    ///
+    /// Cheaper than [`fold_with_stats`](UmlautsOwned::fold_with_stats) if only the presence
+    /// of each umlaut is needed, as nothing is folded and the scan stops once all eight
+    /// umlauts have been found.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::Umlaut;
+    ///
+    /// let umlauts = "Füße".distinct_umlauts();
+    /// assert!(umlauts.contains(Umlaut::LowercaseSharpS));
+    /// assert_eq!(umlauts.to_string(), "üß");
+    /// ```
+    fn distinct_umlauts(&self) -> UmlautSet;
+
+    /// Converts only the umlauts at the start and at the end to ae, oe, ue, ss, ...
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// but only the run of umlauts directly at the start of the text and the run directly
+    /// at its end. Scanning stops at the first byte that is not part of an umlaut, so any
+    /// other character, including whitespace, ends a run. Umlauts in between are left unchanged.
+    /// A text consisting only of umlauts is folded completely.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Übergröße".fold_boundary_umlauts(), "Uebergröße".as_bytes());
+    /// assert_eq!("ÄÖ größer ü".fold_boundary_umlauts(), "AeOe größer ue".as_bytes());
+    /// ```
+    fn fold_boundary_umlauts(&self) -> Vec<u8>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and counts the characters of the original text.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`fold_ascii_keep_case`](UmlautsOwned::fold_ascii_keep_case) and returns the folded
+    /// text together with the number of characters before folding, where every umlaut
+    /// counts as one character. For invalid UTF-8, every byte that is not a continuation
+    /// byte counts as a character.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Grüße".fold_ascii_counted(), (b"Gruesse".to_vec(), 5));
+    /// ```
+    fn fold_ascii_counted(&self) -> (Vec<u8>, usize);
+
+    /// Uppercases alphabetic ASCII chars and UTF-8 umlauts, expanding 'ß' and 'ẞ' to "SS".
+    ///
+    /// Like [`make_utf8_umlauts_uppercase`](crate::UmlautsInplaceExt::make_utf8_umlauts_uppercase),
+    /// but also uppercases 'ß' to "SS", which the in place version can't do, as "SS" is one
+    /// character longer. The result is the usual german all caps spelling without 'ẞ':
+    /// - 'ä' -> 'Ä'
+    /// - 'ö' -> 'Ö'
+    /// - 'ü' -> 'Ü'
+    /// - 'ß' and 'ẞ' -> "SS"
+    ///
+    /// All other non-ASCII characters are left unchanged.
+    /// Use [`Transliterator::uppercase`](crate::Transliterator::uppercase) to keep 'ẞ' instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("straße".to_german_uppercase_keep_umlauts(), b"STRASSE");
+    /// assert_eq!("öl".to_german_uppercase_keep_umlauts(), "ÖL".as_bytes());
+    /// ```
+    fn to_german_uppercase_keep_umlauts(&self) -> Vec<u8>;
+
+    /// Suggests which "ae", "oe", "ue" and "ss" of a folded text likely are folded umlauts.
+    ///
+    /// Returns the byte range of every suggested sequence and the umlaut it likely came from,
+    /// in order, e.g. for a user interface to accept or reject each of them.
+    /// This is a heuristic like [`din2_to_din1_ascii`](UmlautsOwned::din2_to_din1_ascii):
+    /// - "ae", "oe" and "ue" are suggested unless they follow another vowel ("Bauer"),
+    ///   for "ue" after 'q' ("Quelle") and in words containing one of
+    ///   [`LITERAL_VOWEL_PAIR_WORDS`](crate::LITERAL_VOWEL_PAIR_WORDS) ("Michael").
+    /// - "ss" is only suggested inside of one of
+    ///   [`FOLDED_SHARP_S_WORDS`](crate::FOLDED_SHARP_S_WORDS) ("Strasse"), "SS" as 'ẞ'.
+    ///
+    /// The case of the umlaut follows the case of the first letter of the sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use umlauts::Umlaut;
+    ///
+    /// assert_eq!(
+    ///     "Mueller, Bauer, Strasse".suggest_umlaut_restorations(),
+    ///     vec![(1..3, Umlaut::LowercaseU), (20..22, Umlaut::LowercaseSharpS)]
+    /// );
+    /// ```
+    fn suggest_umlaut_restorations(&self) -> Vec<(Range<usize>, Umlaut)>;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... and maps every folded byte to its source character.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`fold_ascii_keep_case`](UmlautsOwned::fold_ascii_keep_case). The second vector holds,
+    /// for every byte of the folded text, the index of the character of the original text it
+    /// belongs to, counted in characters instead of bytes, e.g. for editors tracking cursor
+    /// positions in characters. Both bytes of an expansion map to the umlaut.
+    /// Characters are Unicode scalar values like in [`str::chars`], not grapheme clusters,
+    /// and every invalid byte counts as one character, like in [`fold_trace`](UmlautsOwned::fold_trace).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let (folded, map) = "Maß".fold_ascii_with_grapheme_map();
+    /// assert_eq!(folded, b"Mass");
+    /// assert_eq!(map, vec![0, 1, 2, 2]);
+    /// ```
+    fn fold_ascii_with_grapheme_map(&self) -> (Vec<u8>, Vec<usize>);
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... producing at most `max` bytes.
+    ///
+    /// Maps umlauts according to DIN 5007-2, including the uppercase ß ('ẞ' -> 'SS'),
+    /// and stops before the first replacement or character that does not fit completely,
+    /// so "ß" is never cut to a single 's' and multi-byte characters are never split.
+    /// Unlike [`fold_to_fixed`](UmlautsOwned::fold_to_fixed), the result is not padded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// assert_eq!("Straße".fold_ascii_capped(5), b"Stra");
+    /// assert_eq!("Straße".fold_ascii_capped(6), b"Strass");
+    /// assert_eq!("Straße".fold_ascii_capped(100), b"Strasse");
+    /// ```
+    fn fold_ascii_capped(&self, max: usize) -> Vec<u8>;
+}
+
+impl UmlautsOwned for [u8] {
+    fn koelner_phonetik(&self) -> String {
+        phonetic::koelner_phonetik(self)
+    }
+
+    fn fold_diff(&self) -> FoldDiff<'_> {
+        FoldDiff::new(self)
+    }
+
+    fn fold_to_fixed(&self, width: usize, pad: u8) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(width);
+        fold_din_5007_2_capped(self, width, &mut folded);
+        folded.resize(width, pad);
+        folded
+    }
+
+    fn capitalize_first_umlaut_aware(&self) -> Vec<u8> {
+        case::capitalize_first(self)
+    }
+
+    fn is_ascii_fold_reversible(&self) -> bool {
+        is_fold_reversible(self)
+    }
+
+    fn count_umlauts(&self) -> usize {
+        self.fold_diff().count()
+    }
+
+    fn umlaut_sequence(&self) -> Vec<Umlaut> {
+        self.fold_diff()
+            .filter_map(|(range, _)| Umlaut::from_bytes(&self[range]))
+            .collect()
+    }
+
+    fn fold_ascii_into(&self, out: &mut String) {
+        let mut folded = std::mem::take(out).into_bytes();
+        folded.clear();
+        fold_din_5007_2(self, &mut folded);
+        *out = match String::from_utf8(folded) {
+            Ok(folded) => folded,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        };
+    }
+
+    fn canonical_german(&self) -> Box<str> {
+        normalize::canonical_german(self)
+    }
+
+    fn fold_ascii_word_aware<F, R>(&self, f: F) -> Vec<u8>
+    where
+        F: Fn(bool, Umlaut) -> R,
+        R: AsRef<[u8]>,
+    {
+        let mut folded = Vec::with_capacity(self.len());
+        let mut last = 0;
+        for (range, _) in self.fold_diff() {
+            folded.extend_from_slice(&self[last..range.start]);
+            let at_word_start = match decode_last_char(&self[..range.start]) {
+                Some(prev) => !prev.is_alphanumeric(),
+                None => true,
+            };
+            if let Some(umlaut) = Umlaut::from_bytes(&self[range.clone()]) {
+                folded.extend_from_slice(f(at_word_start, umlaut).as_ref());
+            }
+            last = range.end;
+        }
+        folded.extend_from_slice(&self[last..]);
+        folded
+    }
+
+    fn din2_to_din1_ascii(&self) -> Vec<u8> {
+        reverse::din2_to_din1(self)
+    }
+
+    fn german_display_width(&self) -> usize {
+        let columns = self
+            .iter()
+            .filter(|&&byte| byte & 0xc0 != 0x80 && !byte.is_ascii_control())
+            .count();
+        let diaereses = self
+            .windows(2)
+            .filter(|pair| *pair == normalize::COMBINING_DIAERESIS)
+            .count();
+        columns - diaereses
+    }
+
+    fn fold_ascii_protecting(&self, protected: &[&[u8]]) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(self.len());
+        let mut i = 0;
+        while i < self.len() {
+            let rest = &self[i..];
+            let longest = protected
+                .iter()
+                .filter(|substring| !substring.is_empty() && rest.starts_with(substring))
+                .max_by_key(|substring| substring.len());
+            if let Some(substring) = longest {
+                folded.extend_from_slice(substring);
+                i += substring.len();
+            } else if let Some((len, replacement)) = din_5007_2_at(rest) {
+                folded.extend_from_slice(replacement);
+                i += len;
+            } else {
+                folded.push(rest[0]);
+                i += 1;
+            }
+        }
+        folded
+    }
+
+    fn fold_ascii_strict(&self) -> Result<Vec<u8>, Vec<(usize, char)>> {
+        fold::fold_strict(self)
+    }
+
+    fn fold_alnum_key(&self) -> Vec<u8> {
+        self.fold_key_retaining(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit())
+    }
+
+    fn fold_key_retaining<F>(&self, mut keep: F) -> Vec<u8>
+    where
+        F: FnMut(u8) -> bool,
+    {
+        FoldedBytes::new(self)
+            .map(|byte| byte.to_ascii_lowercase())
+            .filter(|&byte| keep(byte))
+            .collect()
+    }
+
+    fn fold_ascii_into_array<const N: usize>(
+        &self,
+        out: &mut [u8; N],
+    ) -> Result<usize, CapacityError> {
+        array::fold_into_slice(self, out)
+    }
+
+    fn fold_ascii_to_writer<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut last = 0;
+        while let Some((range, replacement)) = next_umlaut(self, last) {
+            writer.write_all(&self[last..range.start])?;
+            writer.write_all(replacement)?;
+            last = range.end;
+        }
+        writer.write_all(&self[last..])
+    }
+
+    fn in_place_fold_is_lossless(&self) -> bool {
+        fold::next_lossy_in_place(self, 0).is_none()
+    }
+
+    fn ascii_prefix_len(&self) -> usize {
+        self.iter()
+            .position(|byte| !byte.is_ascii())
+            .unwrap_or(self.len())
+    }
+
+    fn base_vowel_aligned(&self, filler: u8) -> Vec<u8> {
+        let mut aligned = self.to_vec();
+        let mut last = 0;
+        while let Some((range, replacement)) = next_umlaut(self, last) {
+            // "ae", "oe" and "ue", but not the expansions of 'ß' and 'ẞ'
+            if replacement[1] == b'e' {
+                aligned[range.start] = replacement[0];
+                aligned[range.start + 1] = filler;
+            }
+            last = range.end;
+        }
+        aligned
+    }
+
+    fn folded_tokens(&self) -> FoldedTokens<'_> {
+        FoldedTokens::new(self)
+    }
+
+    #[cfg(feature = "segmentation")]
+    fn german_graphemes(&self) -> GermanGraphemes<'_> {
+        GermanGraphemes::new(self)
+    }
+
+    fn ensure_folded_ascii(&self) -> Result<(), NonAsciiError> {
+        fold::ensure_ascii(self)
+    }
+
+    fn fold_ascii_reversed(&self) -> Vec<u8> {
+        // no replacement is longer than its umlaut, so the folded text fits
+        let mut reversed = vec![0; self.len()];
+        let mut end = reversed.len();
+        let mut rest: &[u8] = self;
+        while !rest.is_empty() {
+            let (result, len) = fold_char_bytes(rest);
+            let folded = result.replacement().unwrap_or(&rest[..len]);
+            reversed[end - folded.len()..end].copy_from_slice(folded);
+            end -= folded.len();
+            rest = &rest[len..];
+        }
+        reversed.drain(..end);
+        reversed
+    }
+
+    fn umlaut_fingerprint(&self) -> u64 {
+        normalize::fingerprint(self)
+    }
+
+    fn fold_and_collapse_ws(&self) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(self.len());
+        normalize::fold_collapsed(self, |byte| folded.push(byte));
+        folded
+    }
+
+    fn is_already_ascii_folded(&self) -> bool {
+        next_umlaut(self, 0).is_none()
+    }
+
+    fn fold_ascii_reporting(&self) -> (Vec<u8>, bool) {
+        let mut folded = Vec::with_capacity(self.len());
+        let changed = fold_din_5007_2(self, &mut folded);
+        (folded, changed)
+    }
+
+    fn to_german_lowercase(&self) -> Vec<u8> {
+        case::german_lowercase(self)
+    }
+
+    fn split_camel_umlaut_aware(&self) -> Vec<&[u8]> {
+        case::split_camel(self)
+    }
+
+    fn to_typeable_ascii(&self, convention: TypeConvention) -> Vec<u8> {
+        typeable::to_typeable(self, convention)
+    }
+
+    fn unify_sharp_s(&self, to_capital: bool) -> Vec<u8> {
+        case::unify_sharp_s(self, to_capital)
+    }
+
+    fn unsafe_inplace_positions(&self) -> Vec<usize> {
+        let mut positions = Vec::new();
+        let mut from = 0;
+        while let Some(position) = fold::next_lossy_in_place(self, from) {
+            positions.push(position);
+            from = position + 1;
+        }
+        positions
+    }
+
+    fn fold_ascii_keep_case(&self) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(self.len());
+        fold_din_5007_2(self, &mut folded);
+        folded
+    }
+
+    fn fold_ascii_string(&self) -> String {
+        match String::from_utf8(self.fold_ascii_keep_case()) {
+            Ok(folded) => folded,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        }
+    }
+
+    fn fold_with_stats(&self) -> (Vec<u8>, UmlautStats) {
+        stats::fold_with_stats(self)
+    }
+
+    #[cfg(feature = "locale_numbers")]
+    fn normalize_german_numbers(&self) -> Vec<u8> {
+        numbers::normalize_german_numbers(self)
+    }
+
+    fn fold_trace(&self) -> FoldTrace<'_> {
+        FoldTrace::new(self)
+    }
+
+    fn fold_ascii_nfd(&self) -> Vec<u8> {
+        normalize::compose_umlauts(self).fold_ascii_keep_case()
+    }
+
+    fn fix_double_encoded_umlauts(&self) -> Vec<u8> {
+        mojibake::fix_double_encoded(self)
+    }
+
+    #[cfg(feature = "bumpalo")]
+    fn fold_ascii_in<'b>(&self, bump: &'b Bump) -> &'b [u8] {
+        let folded = bump.alloc_slice_fill_copy(array::folded_len(self), 0);
+        let written = array::fold_into_slice(self, folded).expect("folded length was computed");
+        debug_assert_eq!(written, folded.len());
+        folded
+    }
+
+    fn to_safe_filename(&self) -> String {
+        FilenameStyle::new().to_safe_filename(self)
+    }
+
+    fn umlauts_to_html_numeric(&self) -> Vec<u8> {
+        let mut escaped = Vec::with_capacity(self.len());
+        let mut last = 0;
+        while let Some((range, _)) = next_umlaut(self, last) {
+            escaped.extend_from_slice(&self[last..range.start]);
+            if let Some(umlaut) = Umlaut::from_bytes(&self[range.start..]) {
+                write!(escaped, "&#{};", u32::from(umlaut.as_char()))
+                    .expect("writing to a Vec never fails");
+            }
+            last = range.end;
+        }
+        escaped.extend_from_slice(&self[last..]);
+        escaped
+    }
+
+    fn distinct_umlauts(&self) -> UmlautSet {
+        stats::distinct_umlauts(self)
+    }
+
+    fn fold_boundary_umlauts(&self) -> Vec<u8> {
+        let mut start = 0;
+        while let Some((len, _)) = din_5007_2_at(&self[start..]) {
+            start += len;
+        }
+        let mut end = self.len();
+        while end > start {
+            // 'ẞ' is the only umlaut with three bytes
+            match (2..=3).find(|&len| {
+                end >= start + len
+                    && din_5007_2_at(&self[end - len..end]).map(|(found, _)| found) == Some(len)
+            }) {
+                Some(len) => end -= len,
+                None => break,
+            }
+        }
+        let mut folded = Vec::with_capacity(self.len() + 8);
+        fold_din_5007_2(&self[..start], &mut folded);
+        folded.extend_from_slice(&self[start..end]);
+        fold_din_5007_2(&self[end..], &mut folded);
+        folded
+    }
+
+    fn fold_ascii_counted(&self) -> (Vec<u8>, usize) {
+        let mut folded = Vec::with_capacity(self.len());
+        let mut chars = 0;
+        let mut i = 0;
+        while i < self.len() {
+            if let Some((len, replacement)) = din_5007_2_at(&self[i..]) {
+                folded.extend_from_slice(replacement);
+                chars += 1;
+                i += len;
+            } else {
+                let byte = self[i];
+                folded.push(byte);
+                // continuation bytes belong to the preceding character
+                chars += usize::from(byte & 0xc0 != 0x80);
+                i += 1;
+            }
+        }
+        (folded, chars)
+    }
+
+    fn to_german_uppercase_keep_umlauts(&self) -> Vec<u8> {
+        Transliterator::new()
+            .sharp_s(SharpSPolicy::Expand)
+            .uppercase(self)
+    }
+
+    fn suggest_umlaut_restorations(&self) -> Vec<(Range<usize>, Umlaut)> {
+        reverse::suggest_restorations(self)
+    }
+
+    fn fold_ascii_with_grapheme_map(&self) -> (Vec<u8>, Vec<usize>) {
+        let mut folded = Vec::with_capacity(self.len());
+        let mut map = Vec::with_capacity(self.len());
+        for (index, (_, _, bytes)) in self.fold_trace().enumerate() {
+            folded.extend_from_slice(bytes);
+            map.resize(folded.len(), index);
+        }
+        (folded, map)
+    }
+
+    fn fold_ascii_capped(&self, max: usize) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(max.min(self.len() * 2));
+        fold_din_5007_2_capped(self, max, &mut folded);
+        folded
+    }
+}
+
+impl UmlautsOwned for str {
+    fn koelner_phonetik(&self) -> String {
+        self.as_bytes().koelner_phonetik()
+    }
+
+    fn fold_diff(&self) -> FoldDiff<'_> {
+        self.as_bytes().fold_diff()
+    }
+
+    fn fold_to_fixed(&self, width: usize, pad: u8) -> Vec<u8> {
+        self.as_bytes().fold_to_fixed(width, pad)
+    }
+
+    fn capitalize_first_umlaut_aware(&self) -> Vec<u8> {
+        self.as_bytes().capitalize_first_umlaut_aware()
+    }
+
+    fn is_ascii_fold_reversible(&self) -> bool {
+        self.as_bytes().is_ascii_fold_reversible()
+    }
+
+    fn count_umlauts(&self) -> usize {
+        self.as_bytes().count_umlauts()
+    }
+
+    fn umlaut_sequence(&self) -> Vec<Umlaut> {
+        self.as_bytes().umlaut_sequence()
+    }
+
+    fn fold_ascii_into(&self, out: &mut String) {
+        self.as_bytes().fold_ascii_into(out)
+    }
+
+    fn canonical_german(&self) -> Box<str> {
+        self.as_bytes().canonical_german()
+    }
+
+    fn fold_ascii_word_aware<F, R>(&self, f: F) -> Vec<u8>
+    where
+        F: Fn(bool, Umlaut) -> R,
+        R: AsRef<[u8]>,
+    {
+        self.as_bytes().fold_ascii_word_aware(f)
+    }
+
+    fn din2_to_din1_ascii(&self) -> Vec<u8> {
+        self.as_bytes().din2_to_din1_ascii()
+    }
+
+    fn german_display_width(&self) -> usize {
+        self.as_bytes().german_display_width()
+    }
+
+    fn fold_ascii_protecting(&self, protected: &[&[u8]]) -> Vec<u8> {
+        self.as_bytes().fold_ascii_protecting(protected)
+    }
+
+    fn fold_ascii_strict(&self) -> Result<Vec<u8>, Vec<(usize, char)>> {
+        self.as_bytes().fold_ascii_strict()
+    }
+
+    fn fold_alnum_key(&self) -> Vec<u8> {
+        self.as_bytes().fold_alnum_key()
+    }
+
+    fn fold_key_retaining<F>(&self, keep: F) -> Vec<u8>
+    where
+        F: FnMut(u8) -> bool,
+    {
+        self.as_bytes().fold_key_retaining(keep)
+    }
+
+    fn fold_ascii_into_array<const N: usize>(
+        &self,
+        out: &mut [u8; N],
+    ) -> Result<usize, CapacityError> {
+        self.as_bytes().fold_ascii_into_array(out)
+    }
+
+    fn fold_ascii_to_writer<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_bytes().fold_ascii_to_writer(writer)
+    }
+
+    fn in_place_fold_is_lossless(&self) -> bool {
+        self.as_bytes().in_place_fold_is_lossless()
+    }
+
+    fn ascii_prefix_len(&self) -> usize {
+        self.as_bytes().ascii_prefix_len()
+    }
+
+    fn base_vowel_aligned(&self, filler: u8) -> Vec<u8> {
+        self.as_bytes().base_vowel_aligned(filler)
+    }
+
+    fn folded_tokens(&self) -> FoldedTokens<'_> {
+        self.as_bytes().folded_tokens()
+    }
+
+    #[cfg(feature = "segmentation")]
+    fn german_graphemes(&self) -> GermanGraphemes<'_> {
+        self.as_bytes().german_graphemes()
+    }
+
+    fn ensure_folded_ascii(&self) -> Result<(), NonAsciiError> {
+        self.as_bytes().ensure_folded_ascii()
+    }
+
+    fn fold_ascii_reversed(&self) -> Vec<u8> {
+        self.as_bytes().fold_ascii_reversed()
+    }
+
+    fn umlaut_fingerprint(&self) -> u64 {
+        self.as_bytes().umlaut_fingerprint()
+    }
+
+    fn fold_and_collapse_ws(&self) -> Vec<u8> {
+        self.as_bytes().fold_and_collapse_ws()
+    }
+
+    fn is_already_ascii_folded(&self) -> bool {
+        self.as_bytes().is_already_ascii_folded()
+    }
+
+    fn fold_ascii_reporting(&self) -> (Vec<u8>, bool) {
+        self.as_bytes().fold_ascii_reporting()
+    }
+
+    fn to_german_lowercase(&self) -> Vec<u8> {
+        self.as_bytes().to_german_lowercase()
+    }
+
+    fn split_camel_umlaut_aware(&self) -> Vec<&[u8]> {
+        self.as_bytes().split_camel_umlaut_aware()
+    }
+
+    fn to_typeable_ascii(&self, convention: TypeConvention) -> Vec<u8> {
+        self.as_bytes().to_typeable_ascii(convention)
+    }
+
+    fn unify_sharp_s(&self, to_capital: bool) -> Vec<u8> {
+        self.as_bytes().unify_sharp_s(to_capital)
+    }
+
+    fn unsafe_inplace_positions(&self) -> Vec<usize> {
+        self.as_bytes().unsafe_inplace_positions()
+    }
+
+    fn fold_ascii_keep_case(&self) -> Vec<u8> {
+        self.as_bytes().fold_ascii_keep_case()
+    }
+
+    fn fold_ascii_string(&self) -> String {
+        self.as_bytes().fold_ascii_string()
+    }
+
+    fn fold_with_stats(&self) -> (Vec<u8>, UmlautStats) {
+        self.as_bytes().fold_with_stats()
+    }
+
+    #[cfg(feature = "locale_numbers")]
+    fn normalize_german_numbers(&self) -> Vec<u8> {
+        self.as_bytes().normalize_german_numbers()
+    }
+
+    fn fold_trace(&self) -> FoldTrace<'_> {
+        self.as_bytes().fold_trace()
+    }
+
+    fn fold_ascii_nfd(&self) -> Vec<u8> {
+        self.as_bytes().fold_ascii_nfd()
+    }
+
+    fn fix_double_encoded_umlauts(&self) -> Vec<u8> {
+        self.as_bytes().fix_double_encoded_umlauts()
+    }
+
+    #[cfg(feature = "bumpalo")]
+    fn fold_ascii_in<'b>(&self, bump: &'b Bump) -> &'b [u8] {
+        self.as_bytes().fold_ascii_in(bump)
+    }
+
+    fn to_safe_filename(&self) -> String {
+        self.as_bytes().to_safe_filename()
+    }
+
+    fn umlauts_to_html_numeric(&self) -> Vec<u8> {
+        self.as_bytes().umlauts_to_html_numeric()
+    }
+
+    fn distinct_umlauts(&self) -> UmlautSet {
+        self.as_bytes().distinct_umlauts()
+    }
+
+    fn fold_boundary_umlauts(&self) -> Vec<u8> {
+        self.as_bytes().fold_boundary_umlauts()
+    }
+
+    fn fold_ascii_counted(&self) -> (Vec<u8>, usize) {
+        self.as_bytes().fold_ascii_counted()
+    }
+
+    fn to_german_uppercase_keep_umlauts(&self) -> Vec<u8> {
+        self.as_bytes().to_german_uppercase_keep_umlauts()
+    }
+
+    fn suggest_umlaut_restorations(&self) -> Vec<(Range<usize>, Umlaut)> {
+        self.as_bytes().suggest_umlaut_restorations()
+    }
+
+    fn fold_ascii_with_grapheme_map(&self) -> (Vec<u8>, Vec<usize>) {
+        self.as_bytes().fold_ascii_with_grapheme_map()
+    }
+
+    fn fold_ascii_capped(&self, max: usize) -> Vec<u8> {
+        self.as_bytes().fold_ascii_capped(max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Umlaut, UmlautsOwned};
+
+    #[test]
+    fn umlaut_sequence() {
+        assert_eq!(
+            "Müller grüßt Öl".umlaut_sequence(),
+            vec![
+                Umlaut::LowercaseU,
+                Umlaut::LowercaseU,
+                Umlaut::LowercaseSharpS,
+                Umlaut::UppercaseO
+            ]
+        );
+        assert_eq!(
+            "ÄÖÜäöüßẞ café".umlaut_sequence(),
+            vec![
+                Umlaut::UppercaseA,
+                Umlaut::UppercaseO,
+                Umlaut::UppercaseU,
+                Umlaut::LowercaseA,
+                Umlaut::LowercaseO,
+                Umlaut::LowercaseU,
+                Umlaut::LowercaseSharpS,
+                Umlaut::UppercaseSharpS
+            ]
+        );
+        assert!("Mueller".umlaut_sequence().is_empty());
+    }
+
+    #[test]
+    fn fold_ascii_word_aware() {
+        let text = "Übermut, „Äpfel“ und Öl-Ölung ÄÖ";
+        let starts = text.fold_ascii_word_aware(|at_word_start, umlaut| {
+            if at_word_start {
+                vec![b'<', umlaut.din_5007_2()[0], b'>']
+            } else {
+                umlaut.din_5007_2().to_vec()
+            }
+        });
+        assert_eq!(
+            starts,
+            "<U>bermut, „<A>pfel“ und <O>l-<O>lung <A>Oe".as_bytes()
+        );
+
+        let text = "GROẞE Straße, 3Äpfel";
+        let folded = text.fold_ascii_word_aware(|_, umlaut| umlaut.din_5007_2());
+        assert_eq!(folded, b"GROSSE Strasse, 3Aepfel");
+        assert_eq!(folded.ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn fold_ascii_protecting() {
+        let text = "Müller-GmbH: Grüße an Müller von https://bäcker.de/ö";
+        let protected: &[&[u8]] = &["Müller-GmbH".as_bytes(), "https://bäcker.de/ö".as_bytes()];
+        assert_eq!(
+            text.fold_ascii_protecting(protected),
+            "Müller-GmbH: Gruesse an Mueller von https://bäcker.de/ö".as_bytes()
+        );
+
+        let protected: &[&[u8]] = &["Mü".as_bytes(), "Müller".as_bytes(), b""];
+        assert_eq!(
+            "Müllerin Mühle".fold_ascii_protecting(protected),
+            "Müllerin Mühle".as_bytes()
+        );
+        assert_eq!("Grüße".fold_ascii_protecting(&[]), b"Gruesse");
+    }
+
+    #[test]
+    fn german_display_width() {
+        assert_eq!("Grüße".german_display_width(), 5);
+        assert_eq!("Gruesse".german_display_width(), 7);
+        assert_eq!("ÄÖÜäöüßẞ".german_display_width(), 8);
+        assert_eq!("Mu\u{308}ller".german_display_width(), 6);
+        assert_eq!("a\tb\n".german_display_width(), 2);
+        assert_eq!("café │".german_display_width(), 6);
+        assert_eq!("".german_display_width(), 0);
+    }
+
+    #[test]
+    fn count_umlauts() {
+        assert_eq!("Müller grüßt Öl".count_umlauts(), 4);
+        assert_eq!("GROẞ é".count_umlauts(), 1);
+        assert_eq!("".count_umlauts(), 0);
+    }
+
+    #[test]
+    fn fold_ascii_into_reuses_string() {
+        let mut out = String::with_capacity(64);
+        let capacity = out.capacity();
+        for (text, folded) in [
+            ("Öl Ärmel Übermut", "Oel Aermel Uebermut"),
+            ("GROẞE Straße", "GROSSE Strasse"),
+            ("café", "café"),
+            ("", ""),
+        ]
+        .iter()
+        {
+            text.fold_ascii_into(&mut out);
+            assert_eq!(out, *folded);
+            assert_eq!(out.capacity(), capacity);
+        }
+        "ÄÖÜäöüßẞ".fold_ascii_into(&mut out);
+        assert_eq!(out.ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn fold_ascii_into_invalid_utf8() {
+        let mut out = String::from("old");
+        [b'a', 0xc3, 0xa4, 0xff].fold_ascii_into(&mut out);
+        assert_eq!(out, "aae\u{fffd}");
+    }
+
+    #[test]
+    fn fold_to_fixed() {
+        assert_eq!("Grüße".fold_to_fixed(4, b' '), b"Grue");
+        assert_eq!("Grüße".fold_to_fixed(3, b' '), b"Gr ");
+        assert_eq!("Grüße".fold_to_fixed(10, b'.'), b"Gruesse...");
+        assert_eq!("GROẞ".fold_to_fixed(4, b' '), b"GRO ");
+        assert_eq!("".fold_to_fixed(2, b' '), b"  ");
+        assert_eq!("Öl".fold_to_fixed(0, b' '), b"");
+        assert_eq!(
+            "ÄÖÜäöüßẞ".fold_to_fixed(20, b' ').ensure_folded_ascii(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fold_alnum_key() {
+        assert_eq!(
+            "Müller & Söhne (2024)".fold_alnum_key(),
+            b"muellersoehne2024"
+        );
+        assert_eq!("GROẞE Straße 1a".fold_alnum_key(), b"grossestrasse1a");
+        assert_eq!("Café".fold_alnum_key(), b"caf");
+        assert_eq!("".fold_alnum_key(), b"");
+        assert_eq!("ÄÖÜäöüßẞ".fold_alnum_key().ensure_folded_ascii(), Ok(()));
+        assert_eq!(
+            "Bäcker-Straße".fold_key_retaining(|byte| byte != b'-'),
+            b"baeckerstrasse"
+        );
+    }
+
+    #[test]
+    fn fold_ascii_into_array_overflow() {
+        let mut buf = [0u8; 12];
+        let err = "GROẞ ßßßß".fold_ascii_into_array(&mut buf).unwrap_err();
+        assert_eq!((err.required(), err.capacity()), (14, 12));
+        assert_eq!("ẞẞẞẞẞẞ".fold_ascii_into_array(&mut buf), Ok(12));
+        assert_eq!(&buf, b"SSSSSSSSSSSS");
+
+        let text = "Fußgängerstraße";
+
+        let mut buf = [0u8; 32];
+        let len = text.fold_ascii_into_array(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"Fussgaengerstrasse");
+        assert_eq!(buf[..len].ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn fold_ascii_to_writer() {
+        let mut out = Vec::new();
+        "GROẞE Grüße, ".fold_ascii_to_writer(&mut out).unwrap();
+        "Café".fold_ascii_to_writer(&mut out).unwrap();
+        assert_eq!(out, "GROSSE Gruesse, Café".as_bytes());
+        let mut out = Vec::new();
+        "ÄÖÜäöüßẞ".fold_ascii_to_writer(&mut out).unwrap();
+        assert_eq!(out.ensure_folded_ascii(), Ok(()));
+
+        let mut buf = [0; 4];
+        let mut small: &mut [u8] = &mut buf;
+        let err = "Öl ist gut".fold_ascii_to_writer(&mut small).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(&buf, b"Oel ");
+    }
+
+    #[test]
+    fn in_place_fold_is_lossless() {
+        use crate::{Transliterator, UmlautsInplaceExt};
+
+        assert!("Öl, Ärger und Straße".in_place_fold_is_lossless());
+        assert!("ß".in_place_fold_is_lossless());
+        assert!("".in_place_fold_is_lossless());
+        assert!(!"ẞ".in_place_fold_is_lossless());
+        assert!(!"STRAßE".in_place_fold_is_lossless());
+        assert!(!"ßE".in_place_fold_is_lossless());
+        assert!(!"Straße und GROß".in_place_fold_is_lossless());
+
+        for text in ["oßE", "Sße", "ÜßE", "GRÜß", "Straße", "GROẞE"].iter() {
+            let mut in_place = text.as_bytes().to_vec();
+            in_place.make_utf8_umlauts_to_ascii();
+            let same = in_place == Transliterator::new().fold_ascii(text.as_bytes());
+            assert_eq!(text.in_place_fold_is_lossless(), same, "{}", text);
+        }
+        assert!(!"oßE".in_place_fold_is_lossless());
+        assert!("Sße".in_place_fold_is_lossless());
+    }
+
+    #[test]
+    fn ascii_prefix_len() {
+        let text = "Das Café in Köln";
+        assert_eq!(text.ascii_prefix_len(), 7);
+        assert_eq!(&text[..text.ascii_prefix_len()], "Das Caf");
+        assert_eq!("ẞ".ascii_prefix_len(), 0);
+        assert_eq!("".ascii_prefix_len(), 0);
+        assert_eq!(b"abc".ascii_prefix_len(), 3);
+    }
+
+    #[test]
+    fn base_vowel_aligned() {
+        let text = "Grüße aus Österreich, GROẞE Bäume";
+        let aligned = text.base_vowel_aligned(b'_');
+        assert_eq!(aligned.len(), text.len());
+        assert_eq!(aligned, "Gru_ße aus O_sterreich, GROẞE Ba_ume".as_bytes());
+        for word in &["aus", "sterreich", "GRO", "ume"] {
+            let start = text.find(word).unwrap();
+            assert_eq!(&aligned[start..start + word.len()], word.as_bytes());
+        }
+        assert_eq!("".base_vowel_aligned(b' '), b"");
+    }
+
+    #[test]
+    fn ensure_folded_ascii() {
+        assert_eq!(b"Gruesse".ensure_folded_ascii(), Ok(()));
+        assert_eq!("".ensure_folded_ascii(), Ok(()));
+        let err = "Grüße".ensure_folded_ascii().unwrap_err();
+        assert_eq!((err.position(), err.byte()), (2, 0xc3));
+        assert_eq!(err.to_string(), "non-ASCII byte 0xc3 at offset 2");
+    }
+
+    /// Box-drawing characters in tables must pass through byte for byte.
+    #[test]
+    fn box_drawing_passthrough() {
+        use crate::{Transliterator, UmlautFoldState, UmlautsInplaceExt, UmlautsVecExt};
+
+        let table = "┌─────┐\n│ Grüße │\n╞═ü═╡\n└─────┘";
+        let expected = "┌─────┐\n│ Gruesse │\n╞═ue═╡\n└─────┘";
+        assert_eq!(table.fold_ascii_keep_case(), expected.as_bytes());
+        // the strict fold rejects exactly the box-drawing characters
+        let rejected = table.fold_ascii_strict().unwrap_err();
+        assert_eq!(rejected.len(), 20);
+        assert!(rejected
+            .iter()
+            .all(|&(i, c)| ('\u{2500}'..='\u{257f}').contains(&c) && table[i..].starts_with(c)));
+
+        // every box-drawing character between umlauts, for every fold
+        for c in '\u{2500}'..='\u{257f}' {
+            let text = format!("ü{}ü{}ẞ", c, c);
+            let expected = format!("ue{}ue{}SS", c, c);
+            assert_eq!(text.fold_ascii_keep_case(), expected.as_bytes(), "{}", c);
+            assert_eq!(
+                Transliterator::new().fold_ascii(text.as_bytes()),
+                expected.as_bytes()
+            );
+            let mut bytes = text.as_bytes().to_vec();
+            bytes.fold_ascii_reusing_capacity();
+            assert_eq!(bytes, expected.as_bytes());
+
+            let mut state = UmlautFoldState::new();
+            let mut out = Vec::new();
+            for chunk in text.as_bytes().chunks(1) {
+                state.push(chunk, &mut out);
+            }
+            state.finish(&mut out);
+            assert_eq!(out, expected.as_bytes());
+
+            let mut bytes = format!("ü{}ü", c).into_bytes();
+            bytes.make_utf8_umlauts_to_ascii();
+            assert_eq!(bytes, format!("ue{}ue", c).as_bytes());
+            bytes.make_utf8_umlauts_to_uppercase_ascii();
+            assert_eq!(bytes, format!("UE{}UE", c).as_bytes());
+            let mut bytes = format!("Ü{}Ü", c).into_bytes();
+            bytes.make_utf8_umlauts_lowercase();
+            assert_eq!(bytes, format!("ü{}ü", c).as_bytes());
+            bytes.make_utf8_umlauts_uppercase();
+            assert_eq!(bytes, format!("Ü{}Ü", c).as_bytes());
+        }
+    }
+
+    /// Folding folded text must not change it again.
+    #[test]
+    fn folds_are_idempotent() {
+        use crate::test_util::Xorshift;
+        use crate::{
+            CapitalExpansion, CasePolicy, SharpSCase, Transliterator, TypeConvention,
+            UmlautFoldState, UmlautsInplaceExt, UmlautsVecExt,
+        };
+
+        type Fold = Box<dyn Fn(&[u8]) -> Vec<u8>>;
+        let mut folds: Vec<(&str, Fold)> = vec![
+            (
+                "fold_ascii_keep_case",
+                Box::new(|text| text.fold_ascii_keep_case()),
+            ),
+            ("fold_ascii_nfd", Box::new(|text| text.fold_ascii_nfd())),
+            ("fold_alnum_key", Box::new(|text| text.fold_alnum_key())),
+            (
+                "fold_and_collapse_ws",
+                Box::new(|text| text.fold_and_collapse_ws()),
+            ),
+            (
+                "fold_to_fixed",
+                Box::new(|text| text.fold_to_fixed(12, b' ')),
+            ),
+            (
+                "fold_ascii_capped",
+                Box::new(|text| text.fold_ascii_capped(12)),
+            ),
+            (
+                "fold_ascii_protecting",
+                Box::new(|text| text.fold_ascii_protecting(&["Ö-ß".as_bytes()])),
+            ),
+            (
+                "fold_ascii_word_aware",
+                Box::new(|text| text.fold_ascii_word_aware(|_, umlaut| umlaut.din_5007_2())),
+            ),
+            (
+                "to_typeable_ascii",
+                Box::new(|text| text.to_typeable_ascii(TypeConvention::DoubleQuotePrefix)),
+            ),
+            (
+                "to_safe_filename",
+                Box::new(|text| text.to_safe_filename().into_bytes()),
+            ),
+            (
+                "canonical_german",
+                Box::new(|text| text.canonical_german().as_bytes().to_vec()),
+            ),
+            (
+                "fold_ascii_reusing_capacity",
+                Box::new(|text| {
+                    let mut bytes = text.to_vec();
+                    bytes.fold_ascii_reusing_capacity();
+                    bytes
+                }),
+            ),
+            (
+                "make_utf8_umlauts_to_ascii",
+                Box::new(|text| {
+                    let mut bytes = text.to_vec();
+                    bytes.make_utf8_umlauts_to_ascii();
+                    bytes
+                }),
+            ),
+            (
+                "UmlautFoldState",
+                Box::new(|text| {
+                    let mut state = UmlautFoldState::new();
+                    let mut out = Vec::new();
+                    for chunk in text.chunks(3) {
+                        state.push(chunk, &mut out);
+                    }
+                    state.finish(&mut out);
+                    out
+                }),
+            ),
+        ];
+        for &case in &[CasePolicy::Preserve, CasePolicy::Lower, CasePolicy::Upper] {
+            for &expansion in &[
+                CapitalExpansion::Title,
+                CapitalExpansion::FollowContext,
+                CapitalExpansion::Upper,
+            ] {
+                for &sharp_s in &[
+                    SharpSCase::AlwaysLower,
+                    SharpSCase::FollowContext,
+                    SharpSCase::AlwaysUpper,
+                ] {
+                    let transliterator = Transliterator::new()
+                        .case(case)
+                        .capital_expansion(expansion)
+                        .sharp_s_case(sharp_s);
+                    folds.push((
+                        "Transliterator::fold_ascii",
+                        Box::new(move |text| transliterator.fold_ascii(text)),
+                    ));
+                }
+            }
+        }
+
+        // umlauts, decomposed umlauts, their bases and parts of broken UTF-8
+        let alphabet: [&[u8]; 20] = [
+            b"a",
+            b"O",
+            b"u",
+            b"e",
+            b"S",
+            b" ",
+            b"-",
+            "ä".as_bytes(),
+            "Ö".as_bytes(),
+            "ü".as_bytes(),
+            "ß".as_bytes(),
+            "ẞ".as_bytes(),
+            "\u{308}".as_bytes(),
+            "æ".as_bytes(),
+            "µ".as_bytes(),
+            "é".as_bytes(),
+            &[0xc3],
+            &[0xa4],
+            &[0xe1],
+            &[0xba],
+        ];
+        let mut rng = Xorshift::new(0x9e37_79b9);
+        for len in 0..300 {
+            let text: Vec<u8> = (0..len % 24)
+                .flat_map(|_| rng.pick(&alphabet).iter().copied())
+                .collect();
+            for (name, fold) in folds.iter() {
+                let once = fold(&text);
+                assert_eq!(fold(&once), once, "{} of {:x?}", name, text);
+            }
+        }
+    }
+
+    #[test]
+    fn fold_ascii_reversed() {
+        assert_eq!("Grüße".fold_ascii_reversed(), b"essuerG");
+        assert_eq!("GROẞ".fold_ascii_reversed(), b"SSORG");
+        // other characters are reversed as a whole
+        assert_eq!("é🦀ä".fold_ascii_reversed(), "ae🦀é".as_bytes());
+        assert_eq!([b'a', 0xc3, 0xe1].fold_ascii_reversed(), [0xe1, 0xc3, b'a']);
+        assert_eq!("".fold_ascii_reversed(), b"");
+
+        // unlike reversing the folded text, the expansions keep their order
+        assert_eq!("Öl Ärmel".fold_ascii_reversed(), b"lemrAe lOe");
+    }
+
+    #[test]
+    fn fold_and_collapse_ws() {
+        assert_eq!(
+            "\tMüller  &\r\nSöhne ".fold_and_collapse_ws(),
+            b"mueller & soehne"
+        );
+        assert_eq!("GROẞE".fold_and_collapse_ws(), b"grosse");
+        assert_eq!("   ".fold_and_collapse_ws(), b"");
+    }
+
+    #[test]
+    fn is_already_ascii_folded() {
+        for text in ["Öl", "GROẞE", "Straße", "Müller, café"].iter() {
+            assert!(!text.is_already_ascii_folded());
+            let folded = text
+                .fold_ascii_strict()
+                .unwrap_or_else(|_| text.fold_alnum_key());
+            assert!(folded.is_already_ascii_folded());
+        }
+        assert!("".is_already_ascii_folded());
+        assert!([b'a', 0xc3].is_already_ascii_folded());
+    }
+
+    #[test]
+    fn fold_ascii_reporting() {
+        assert_eq!("ẞ".fold_ascii_reporting(), (b"SS".to_vec(), true));
+        assert_eq!(
+            "ÄÖÜäöüßẞ".fold_ascii_reporting().0.ensure_folded_ascii(),
+            Ok(())
+        );
+        assert_eq!(
+            "Öl, café".fold_ascii_reporting(),
+            ("Oel, café".as_bytes().to_vec(), true)
+        );
+        assert_eq!(
+            "Oel, café".fold_ascii_reporting(),
+            ("Oel, café".as_bytes().to_vec(), false)
+        );
+        assert_eq!("".fold_ascii_reporting(), (Vec::new(), false));
+        let (folded, changed) = [b'a', 0xc3].fold_ascii_reporting();
+        assert_eq!(folded, [b'a', 0xc3]);
+        assert!(!changed);
+    }
+
+    #[test]
+    fn unsafe_inplace_positions() {
+        use crate::{Transliterator, UmlautsInplaceExt};
+
+        assert_eq!("ẞẞ".unsafe_inplace_positions(), vec![0, 3]);
+        assert_eq!("Straße STRAßE".unsafe_inplace_positions(), vec![12]);
+        assert!("Öl, Maß".unsafe_inplace_positions().is_empty());
+        assert!("".unsafe_inplace_positions().is_empty());
+        for text in ["GROẞE", "GROß", "Grüße", "Maß", "oßE", "Sße", "ÜßE", "Öl"].iter() {
+            assert_eq!(
+                text.in_place_fold_is_lossless(),
+                text.unsafe_inplace_positions().is_empty()
+            );
+            let mut in_place = text.as_bytes().to_vec();
+            in_place.make_utf8_umlauts_to_ascii();
+            assert_eq!(
+                text.unsafe_inplace_positions().is_empty(),
+                in_place == Transliterator::new().fold_ascii(text.as_bytes()),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn fold_ascii_keep_case() {
+        assert_eq!(
+            "Müller-Lüdenscheidt".fold_ascii_keep_case(),
+            b"Mueller-Luedenscheidt"
+        );
+        assert_eq!("Öl ÖL öl".fold_ascii_keep_case(), b"Oel OeL oel");
+        assert_eq!("mAß MAẞ".fold_ascii_keep_case(), b"mAss MASS");
+        assert_eq!("café".fold_ascii_keep_case(), "café".as_bytes());
+        assert_eq!(
+            "ÄÖÜäöüßẞ".fold_ascii_keep_case().ensure_folded_ascii(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fold_ascii_string() {
+        assert_eq!("GROẞE Grüße".fold_ascii_string(), "GROSSE Gruesse");
+        assert_eq!("Öl, café".fold_ascii_string(), "Oel, café");
+        assert_eq!("".fold_ascii_string(), "");
+        assert_eq!("ÄÖÜäöüßẞ".fold_ascii_string().ensure_folded_ascii(), Ok(()));
+        assert_eq!(
+            [0xe1, 0xba, b'x', 0xc3, 0xa4].fold_ascii_string(),
+            "\u{fffd}xae"
+        );
+    }
+
+    #[test]
+    fn fold_trace_matches_fold() {
+        for text in ["GROẞE Grüße, café", "", "🦀 Öl"].iter() {
+            let folded: Vec<u8> = text
+                .fold_trace()
+                .flat_map(|(_, _, folded)| folded.iter().copied())
+                .collect();
+            assert_eq!(folded, text.fold_ascii_keep_case());
+            let chars: String = text.fold_trace().map(|(_, c, _)| c).collect();
+            assert_eq!(chars, *text);
+            let offsets: Vec<_> = text.fold_trace().map(|(i, _, _)| i).collect();
+            let expected: Vec<_> = text.char_indices().map(|(i, _)| i).collect();
+            assert_eq!(offsets, expected);
+        }
+    }
+
+    #[test]
+    fn fold_ascii_nfd() {
+        // 'ñ' (NFD) is kept, 'ö' (NFD) is folded
+        assert_eq!(
+            "Jalapen\u{303}o mit O\u{308}l".fold_ascii_nfd(),
+            "Jalapen\u{303}o mit Oel".as_bytes()
+        );
+        assert_eq!(
+            "n\u{308} e\u{308}".fold_ascii_nfd(),
+            "n\u{308} e\u{308}".as_bytes()
+        );
+        assert_eq!(
+            "A\u{308}U\u{308}u\u{308}\u{301}".fold_ascii_nfd(),
+            "AeUeue\u{301}".as_bytes()
+        );
+        assert_eq!("ÖL".fold_ascii_nfd(), b"OeL");
+        assert_eq!("ÄÖÜäöüßẞ".fold_ascii_nfd().ensure_folded_ascii(), Ok(()));
+        assert_eq!([b'u', 0xcc].fold_ascii_nfd(), [b'u', 0xcc]);
+    }
+
+    #[test]
+    #[cfg(feature = "bumpalo")]
+    fn fold_ascii_in() {
+        let bump = bumpalo::Bump::new();
+        let folded: Vec<&[u8]> = ["GROẞE Grüße", "", "café"]
+            .iter()
+            .map(|text| text.fold_ascii_in(&bump))
+            .collect();
+        assert_eq!(folded, vec![&b"GROSSE Gruesse"[..], b"", "café".as_bytes()]);
+        assert_eq!([b'a', 0xc3].fold_ascii_in(&bump), [b'a', 0xc3]);
+        assert_eq!(
+            "ÄÖÜäöüßẞ".fold_ascii_in(&bump).ensure_folded_ascii(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn to_safe_filename() {
+        assert_eq!("Größe/Übung?.txt".to_safe_filename(), "Groesse_Uebung_.txt");
+        assert_eq!(b"C:\\Users".to_safe_filename(), "C__Users");
+    }
+
+    #[test]
+    fn umlauts_to_html_numeric() {
+        let references = [
+            ("ä", "&#228;"),
+            ("ö", "&#246;"),
+            ("ü", "&#252;"),
+            ("Ä", "&#196;"),
+            ("Ö", "&#214;"),
+            ("Ü", "&#220;"),
+            ("ß", "&#223;"),
+            ("ẞ", "&#7838;"),
+        ];
+        for (umlaut, reference) in references.iter() {
+            assert_eq!(umlaut.umlauts_to_html_numeric(), reference.as_bytes());
+        }
+        assert_eq!(
+            "<b>Köln</b> & café".umlauts_to_html_numeric(),
+            "<b>K&#246;ln</b> & café".as_bytes()
+        );
+        assert_eq!([0xc3, b'a'].umlauts_to_html_numeric(), [0xc3, b'a']);
+        assert_eq!("".umlauts_to_html_numeric(), b"");
+    }
+
+    #[test]
+    fn fold_boundary_umlauts() {
+        let fold = |text: &str| String::from_utf8(text.fold_boundary_umlauts()).unwrap();
+        assert_eq!(fold("Ärgernüsse"), "Aergernüsse");
+        assert_eq!(fold("Übelkeitsgefühl für Öl"), "Uebelkeitsgefühl für Öl");
+        assert_eq!(fold("ÄpfelsüßẞÜ"), "AepfelsuessSSUe");
+        assert_eq!(fold("Öäxü ß"), "Oeaexü ss");
+        assert_eq!(fold("ẞäö"), "SSaeoe");
+        assert_eq!(fold("Köln"), "Köln");
+        assert_eq!(fold(""), "");
+        // a broken umlaut ends the run
+        assert_eq!(
+            [0xc3, 0xbc, 0xba, 0xc3, 0xa4].fold_boundary_umlauts(),
+            [b'u', b'e', 0xba, b'a', b'e']
+        );
+        assert_eq!([0xba, 0x9e].fold_boundary_umlauts(), [0xba, 0x9e]);
+    }
+
+    #[test]
+    fn fold_ascii_counted() {
+        assert_eq!("Grüße".fold_ascii_counted(), (b"Gruesse".to_vec(), 5));
+        for text in ["GROẞE Straße", "Öl für 5 €", "🦀 café", ""].iter() {
+            let (folded, chars) = text.fold_ascii_counted();
+            assert_eq!(folded, text.fold_ascii_keep_case());
+            assert_eq!(chars, text.chars().count());
+        }
+        assert_eq!([b'a', 0xc3, 0xc3, 0xa4].fold_ascii_counted().1, 3);
+        assert_eq!(
+            "ÄÖÜäöüßẞ".fold_ascii_counted().0.ensure_folded_ascii(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn to_german_uppercase_keep_umlauts() {
+        let upper =
+            |text: &str| String::from_utf8(text.to_german_uppercase_keep_umlauts()).unwrap();
+        assert_eq!(upper("straße"), "STRASSE");
+        assert_eq!(upper("öl"), "ÖL");
+        assert_eq!(
+            upper("Grüße aus Köln, GROẞE Ärmel"),
+            "GRÜSSE AUS KÖLN, GROSSE ÄRMEL"
+        );
+        assert_eq!(upper("café"), "CAFé");
+        assert_eq!(upper(""), "");
+        assert!(!upper("ßẞ").contains('ẞ'));
+    }
+
+    #[test]
+    fn fold_ascii_with_grapheme_map() {
+        let text = "Straße €ü";
+        let (folded, map) = text.fold_ascii_with_grapheme_map();
+        assert_eq!(folded, "Strasse €ue".as_bytes());
+        assert_eq!(map, vec![0, 1, 2, 3, 4, 4, 5, 6, 7, 7, 7, 8, 8]);
+        let chars: Vec<char> = text.chars().collect();
+        assert_eq!(chars[map[4]], 'ß');
+        assert_eq!(chars[map[12]], 'ü');
+        assert_eq!([0xff, b'a'].fold_ascii_with_grapheme_map().1, vec![0, 1]);
+        assert_eq!("".fold_ascii_with_grapheme_map(), (Vec::new(), Vec::new()));
+        assert_eq!(
+            "ÄÖÜäöüßẞ"
+                .fold_ascii_with_grapheme_map()
+                .0
+                .ensure_folded_ascii(),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn fold_ascii_capped() {
+        let text = "Fuß€ẞ";
+        let capped: Vec<Vec<u8>> = (0..=11).map(|max| text.fold_ascii_capped(max)).collect();
+        assert_eq!(capped[0], b"");
+        assert_eq!(capped[2], b"Fu");
+        // 'ß' -> "ss" fits only completely
+        assert_eq!(capped[3], b"Fu");
+        assert_eq!(capped[4], b"Fuss");
+        // '€' is never split
+        assert_eq!(capped[6], b"Fuss");
+        assert_eq!(capped[7], "Fuss€".as_bytes());
+        assert_eq!(capped[8], "Fuss€".as_bytes());
+        assert_eq!(capped[9], "Fuss€SS".as_bytes());
+        assert_eq!(capped[11], "Fuss€SS".as_bytes());
+        for (max, folded) in capped.iter().enumerate() {
+            assert!(folded.len() <= max);
+            assert!(text.fold_ascii_keep_case().starts_with(folded));
+        }
+        assert_eq!(
+            "ÄÖÜäöüßẞ".fold_ascii_capped(12).ensure_folded_ascii(),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Phonetic codes for german words.
+
+/// Uppercases ASCII letters and maps umlauts to their base letters.
+///
+/// Everything that is not a letter is dropped, so that the phonetic rules
+/// only ever have to look at the neighbouring letters.
+fn letters(bytes: &[u8]) -> Vec<u8> {
+    let mut letters = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1), bytes.get(i + 2)) {
+            (b'a'..=b'z', _, _) | (b'A'..=b'Z', _, _) => {
+                letters.push(bytes[i].to_ascii_uppercase())
+            }
+            // ä, Ä
+            (0xc3, Some(0xa4), _) | (0xc3, Some(0x84), _) => letters.push(b'A'),
+            // ö, Ö
+            (0xc3, Some(0xb6), _) | (0xc3, Some(0x96), _) => letters.push(b'O'),
+            // ü, Ü
+            (0xc3, Some(0xbc), _) | (0xc3, Some(0x9c), _) => letters.push(b'U'),
+            // ß
+            (0xc3, Some(0x9f), _) => letters.push(b'S'),
+            // ẞ
+            (0xe1, Some(0xba), Some(0x9e)) => letters.push(b'S'),
+            _ => {}
+        }
+        i += 1;
+    }
+    letters
+}
+
+/// Decides if a 'C' is pronounced like a 'K' (code 4) or like a 'Z' (code 8).
+fn c_is_hard(prev: Option<u8>, next: Option<u8>) -> bool {
+    match prev {
+        None => matches!(
+            next,
+            Some(b'A' | b'H' | b'K' | b'L' | b'O' | b'Q' | b'R' | b'U' | b'X')
+        ),
+        Some(b'S' | b'Z') => false,
+        Some(_) => matches!(next, Some(b'A' | b'H' | b'K' | b'O' | b'Q' | b'U' | b'X')),
+    }
+}
+
+/// Computes the Kölner Phonetik code.
+///
+/// See [`UmlautsOwned::koelner_phonetik`](crate::UmlautsOwned::koelner_phonetik).
+pub(crate) fn koelner_phonetik(bytes: &[u8]) -> String {
+    let letters = letters(bytes);
+    let mut codes = Vec::with_capacity(letters.len() * 2);
+    for (i, &c) in letters.iter().enumerate() {
+        let prev = if i > 0 { Some(letters[i - 1]) } else { None };
+        let next = letters.get(i + 1).copied();
+        match c {
+            b'A' | b'E' | b'I' | b'J' | b'O' | b'U' | b'Y' => codes.push(b'0'),
+            b'H' => {}
+            b'B' => codes.push(b'1'),
+            b'P' if next == Some(b'H') => codes.push(b'3'),
+            b'P' => codes.push(b'1'),
+            b'D' | b'T' if matches!(next, Some(b'C' | b'S' | b'Z')) => codes.push(b'8'),
+            b'D' | b'T' => codes.push(b'2'),
+            b'F' | b'V' | b'W' => codes.push(b'3'),
+            b'G' | b'K' | b'Q' => codes.push(b'4'),
+            b'C' if c_is_hard(prev, next) => codes.push(b'4'),
+            b'C' => codes.push(b'8'),
+            b'X' if matches!(prev, Some(b'C' | b'K' | b'Q')) => codes.push(b'8'),
+            b'X' => codes.extend_from_slice(b"48"),
+            b'L' => codes.push(b'5'),
+            b'M' | b'N' => codes.push(b'6'),
+            b'R' => codes.push(b'7'),
+            b'S' | b'Z' => codes.push(b'8'),
+            _ => {}
+        }
+    }
+
+    // collapse repeated codes, then drop every '0' except a leading one
+    let mut result = String::with_capacity(codes.len());
+    let mut last = None;
+    for (i, &code) in codes.iter().enumerate() {
+        if last != Some(code) && (code != b'0' || i == 0) {
+            result.push(code as char);
+        }
+        last = Some(code);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::koelner_phonetik;
+    use crate::UmlautsOwned;
+
+    #[test]
+    fn reference_codes() {
+        assert_eq!(
+            koelner_phonetik("Müller-Lüdenscheidt".as_bytes()),
+            "65752682"
+        );
+        assert_eq!(koelner_phonetik(b"Wikipedia"), "3412");
+        assert_eq!(koelner_phonetik(b"Breschnew"), "17863");
+        assert_eq!(koelner_phonetik(b"Anna"), "06");
+        assert_eq!(koelner_phonetik(b"Xaver"), "4837");
+        assert_eq!(koelner_phonetik(b"Philipp"), "351");
+        assert_eq!(koelner_phonetik(b"Christoph"), "47823");
+        assert_eq!(koelner_phonetik(b"Celle"), "85");
+    }
+
+    #[test]
+    fn umlaut_spellings() {
+        let code = koelner_phonetik("Müller".as_bytes());
+        assert_eq!(code, "657");
+        assert_eq!(koelner_phonetik(b"Mueller"), code);
+        assert_eq!(koelner_phonetik(b"Miller"), code);
+        assert_eq!(
+            koelner_phonetik("Straße".as_bytes()),
+            koelner_phonetik(b"Strasse")
+        );
+        assert_eq!(
+            koelner_phonetik("STRAẞE".as_bytes()),
+            koelner_phonetik(b"Strasse")
+        );
+        let code = koelner_phonetik("ÄÖÜäöüßẞ".as_bytes());
+        assert_eq!(code.ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(koelner_phonetik(b""), "");
+        assert_eq!(koelner_phonetik(b"123 -"), "");
+    }
+}
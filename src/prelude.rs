@@ -1 +1,4 @@
-pub use crate::UmlautsInplaceExt;
\ No newline at end of file
+pub use crate::UmlautsInplaceExt;
+pub use crate::UmlautsOwned;
+pub use crate::UmlautsStrExt;
+pub use crate::UmlautsVecExt;
@@ -0,0 +1,226 @@
+//! Best-effort reversal of DIN 5007-2 folding.
+//!
+//! A folded 'ae', 'oe' or 'ue' can't be told apart from a literal one in general,
+//! so the functions in this module rely on heuristics and small word lists.
+use crate::Umlaut;
+use std::ops::Range;
+
+/// Words, lowercase and without umlauts, written with a literal 'ae', 'oe' or 'ue'
+/// that the heuristics would otherwise mistake for a folded umlaut.
+///
+/// A word containing one of these is never changed.
+pub const LITERAL_VOWEL_PAIR_WORDS: &[&str] = &[
+    "aero", "michael", "raphael", "israel", "poet", "poesie", "koexist", "koeff", "duell", "duett",
+    "manuel", "samuel", "suez", "joel", "zoe", "oboe",
+    // adjectives like "eventuell", "manuell", "visuell" and "sexuell", but not "Mueller"
+    "tuell", "anuell", "suell", "xuell",
+];
+
+/// Common words, lowercase and folded according to DIN 5007-2, whose 'ss' is a folded 'ß'.
+///
+/// Used by [`UmlautsOwned::suggest_umlaut_restorations`](crate::UmlautsOwned::suggest_umlaut_restorations),
+/// which suggests 'ß' only inside of one of these. Words with a literal 'ss' like "Wasser"
+/// are far more common than words with an 'ß', so 'ss' is never suggested otherwise.
+pub const FOLDED_SHARP_S_WORDS: &[&str] = &[
+    "strasse", "gruess", "gruss", "gross", "fuss", "fuess", "suess", "heiss", "weiss", "fleiss",
+    "spass", "schliess", "giess", "aussen", "ausser", "bloss", "strauss", "gemaess", "massstab",
+];
+
+/// Checks if `word[i..]` starts with an 'ae', 'oe' or 'ue' that likely is a folded umlaut.
+///
+/// `word` has to be in lowercase.
+pub(crate) fn is_folded_vowel_pair(word: &[u8], i: usize) -> bool {
+    let prev = i.checked_sub(1).map(|prev| word[prev]);
+    match word.get(i..i + 2) {
+        Some(b"ae") | Some(b"oe") | Some(b"ue") => {}
+        _ => return false,
+    }
+    match prev {
+        // "Bauer", "neue", "Feuer"
+        Some(b'a' | b'e' | b'i' | b'o' | b'u') => false,
+        // "Quelle"
+        Some(b'q') if word[i] == b'u' => false,
+        _ => true,
+    }
+}
+
+/// Checks if `word` contains one of [`LITERAL_VOWEL_PAIR_WORDS`].
+///
+/// `word` has to be in lowercase.
+pub(crate) fn has_literal_vowel_pair(word: &[u8]) -> bool {
+    LITERAL_VOWEL_PAIR_WORDS.iter().any(|literal| {
+        word.windows(literal.len())
+            .any(|window| window == literal.as_bytes())
+    })
+}
+
+/// Converts the DIN 5007-2 folding 'ae', 'oe', 'ue' to the DIN 5007-1 folding 'a', 'o', 'u'.
+///
+/// See [`UmlautsOwned::din2_to_din1_ascii`](crate::UmlautsOwned::din2_to_din1_ascii).
+pub(crate) fn din2_to_din1(bytes: &[u8]) -> Vec<u8> {
+    let mut din1 = Vec::with_capacity(bytes.len());
+    for word in bytes.split_inclusive(|byte| !byte.is_ascii_alphabetic()) {
+        let lower = word.to_ascii_lowercase();
+        if has_literal_vowel_pair(&lower) {
+            din1.extend_from_slice(word);
+            continue;
+        }
+        let mut i = 0;
+        while i < word.len() {
+            din1.push(word[i]);
+            i += if is_folded_vowel_pair(&lower, i) {
+                2
+            } else {
+                1
+            };
+        }
+    }
+    din1
+}
+
+/// Suggests the folded umlauts of `bytes` that likely are restorable.
+///
+/// See [`UmlautsOwned::suggest_umlaut_restorations`](crate::UmlautsOwned::suggest_umlaut_restorations).
+pub(crate) fn suggest_restorations(bytes: &[u8]) -> Vec<(Range<usize>, Umlaut)> {
+    let mut suggestions = Vec::new();
+    let mut start = 0;
+    for word in bytes.split_inclusive(|byte| !byte.is_ascii_alphabetic()) {
+        let lower = word.to_ascii_lowercase();
+        if !has_literal_vowel_pair(&lower) {
+            let mut i = 0;
+            while i < word.len() {
+                if is_folded_vowel_pair(&lower, i) {
+                    let umlaut = match (lower[i], word[i].is_ascii_uppercase()) {
+                        (b'a', false) => Umlaut::LowercaseA,
+                        (b'o', false) => Umlaut::LowercaseO,
+                        (b'u', false) => Umlaut::LowercaseU,
+                        (b'a', true) => Umlaut::UppercaseA,
+                        (b'o', true) => Umlaut::UppercaseO,
+                        _ => Umlaut::UppercaseU,
+                    };
+                    suggestions.push((start + i..start + i + 2, umlaut));
+                    i += 2;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        for sharp_s_word in FOLDED_SHARP_S_WORDS {
+            let sharp_s_word = sharp_s_word.as_bytes();
+            for (at, window) in lower.windows(sharp_s_word.len()).enumerate() {
+                if window != sharp_s_word {
+                    continue;
+                }
+                let ss = at
+                    + sharp_s_word
+                        .windows(2)
+                        .position(|pair| pair == b"ss")
+                        .unwrap_or(0);
+                let umlaut = if &word[ss..ss + 2] == b"SS" {
+                    Umlaut::UppercaseSharpS
+                } else {
+                    Umlaut::LowercaseSharpS
+                };
+                suggestions.push((start + ss..start + ss + 2, umlaut));
+            }
+        }
+        start += word.len();
+    }
+    suggestions.sort_by_key(|(range, _)| range.start);
+    suggestions.dedup_by_key(|(range, _)| range.start);
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{din2_to_din1, suggest_restorations, FOLDED_SHARP_S_WORDS};
+    use crate::Umlaut;
+
+    fn din1(text: &str) -> String {
+        String::from_utf8(din2_to_din1(text.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn folded_umlauts() {
+        assert_eq!(din1("Mueller"), "Muller");
+        assert_eq!(din1("Oel, Aerger, Uebermut"), "Ol, Arger, Ubermut");
+        assert_eq!(din1("OEL AERGER"), "OL ARGER");
+        assert_eq!(din1("Baeckerei"), "Backerei");
+        assert_eq!(din1("Strasse"), "Strasse");
+    }
+
+    #[test]
+    fn literal_vowel_pairs() {
+        assert_eq!(din1("Bauer"), "Bauer");
+        assert_eq!(din1("neue Feuer"), "neue Feuer");
+        assert_eq!(din1("Quelle"), "Quelle");
+        assert_eq!(din1("Michael und Raphael"), "Michael und Raphael");
+        assert_eq!(din1("Poet Duell"), "Poet Duell");
+        assert_eq!(
+            din1("eventuell aktuell virtuell manuell visuell individuell"),
+            "eventuell aktuell virtuell manuell visuell individuell"
+        );
+        assert_eq!(din1("Koeffizient"), "Koeffizient");
+        assert_eq!(din1(""), "");
+    }
+
+    #[test]
+    fn not_idempotent() {
+        assert_eq!(din1("Maeer"), "Maer");
+        assert_eq!(din1("Maer"), "Mar");
+    }
+
+    fn restored(text: &str) -> String {
+        let mut restored = String::new();
+        let mut last = 0;
+        for (range, umlaut) in suggest_restorations(text.as_bytes()) {
+            restored.push_str(&text[last..range.start]);
+            restored.push(umlaut.as_char());
+            last = range.end;
+        }
+        restored.push_str(&text[last..]);
+        restored
+    }
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(
+            suggest_restorations(b"Mueller"),
+            vec![(1..3, Umlaut::LowercaseU)]
+        );
+        assert_eq!(restored("Mueller, Bauer"), "Müller, Bauer");
+        assert_eq!(restored("Oelmueller aus Koeln"), "Ölmüller aus Köln");
+        assert_eq!(
+            restored("Gruesse aus der Hauptstrasse"),
+            "Grüße aus der Hauptstraße"
+        );
+        assert_eq!(restored("GROSSE STRASSE"), "GROẞE STRAẞE");
+        assert_eq!(
+            restored("Michael Wasser, neue Quelle"),
+            "Michael Wasser, neue Quelle"
+        );
+        assert_eq!(
+            restored("Schroeder, Jaeger, Strauss"),
+            "Schröder, Jäger, Strauß"
+        );
+        assert_eq!(restored("Masse im Massstab"), "Masse im Maßstab");
+        assert_eq!(
+            restored("eventuell aktuell virtuell, Koeffizient"),
+            "eventuell aktuell virtuell, Koeffizient"
+        );
+        assert_eq!(restored("aktuell Mueller"), "aktuell Müller");
+        assert_eq!(suggest_restorations(b""), vec![]);
+    }
+
+    #[test]
+    fn sharp_s_words_are_folded() {
+        for word in FOLDED_SHARP_S_WORDS {
+            assert!(word.contains("ss"), "{}", word);
+            assert!(
+                word.bytes().all(|byte| byte.is_ascii_lowercase()),
+                "{}",
+                word
+            );
+        }
+    }
+}
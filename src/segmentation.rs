@@ -0,0 +1,87 @@
+//! Grapheme segmentation for decomposed umlauts.
+use unicode_segmentation::{Graphemes, UnicodeSegmentation};
+
+/// Iterator over the extended grapheme clusters of a text.
+///
+/// A base vowel and a following combining diaeresis (NFD) are yielded together.
+/// Bytes that are not valid UTF-8 are yielded as separate items.
+///
+/// Created by [`UmlautsOwned::german_graphemes`](crate::UmlautsOwned::german_graphemes).
+/// Requires the `segmentation` feature.
+#[derive(Debug, Clone)]
+pub struct GermanGraphemes<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    graphemes: Option<Graphemes<'a>>,
+}
+
+impl<'a> GermanGraphemes<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> GermanGraphemes<'a> {
+        GermanGraphemes {
+            bytes,
+            pos: 0,
+            graphemes: None,
+        }
+    }
+}
+
+impl<'a> Iterator for GermanGraphemes<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(grapheme) = self.graphemes.as_mut().and_then(Iterator::next) {
+            return Some(grapheme.as_bytes());
+        }
+        self.graphemes = None;
+
+        let rest = &self.bytes[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        let (valid, invalid_len) = match std::str::from_utf8(rest) {
+            Ok(valid) => (valid, 0),
+            Err(err) => {
+                let valid = std::str::from_utf8(&rest[..err.valid_up_to()]).unwrap();
+                (valid, err.error_len().unwrap_or(rest.len() - valid.len()))
+            }
+        };
+        if valid.is_empty() {
+            self.pos += invalid_len;
+            return Some(&rest[..invalid_len]);
+        }
+        self.pos += valid.len();
+        self.graphemes = Some(valid.graphemes(true));
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GermanGraphemes;
+
+    fn graphemes(bytes: &[u8]) -> Vec<&[u8]> {
+        GermanGraphemes::new(bytes).collect()
+    }
+
+    #[test]
+    fn decomposed_umlaut() {
+        let text = "Ko\u{308}ln";
+        assert_eq!(
+            graphemes(text.as_bytes()),
+            vec![&b"K"[..], "o\u{308}".as_bytes(), &b"l"[..], &b"n"[..],]
+        );
+        assert_eq!(
+            graphemes("öß".as_bytes()),
+            vec!["ö".as_bytes(), "ß".as_bytes()]
+        );
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        assert_eq!(
+            graphemes(&[b'a', 0xc3, b'o', 0xcc, 0x88, 0xe1, 0xba]),
+            vec![&b"a"[..], &[0xc3], "o\u{308}".as_bytes(), &[0xe1, 0xba]]
+        );
+        assert!(graphemes(b"").is_empty());
+    }
+}
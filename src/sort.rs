@@ -0,0 +1,222 @@
+//! Sorting of german words.
+use crate::case::german_lowercase;
+use crate::fold::fold_din_5007_2;
+use std::cmp::Ordering;
+
+/// The collation used by [`sort_german`] and [`german_sort_key`].
+///
+/// All styles ignore the case of ASCII letters and umlauts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortStyle {
+    /// Phone book order of DIN 5007-2: 'ä' sorts as "ae", 'ö' as "oe", 'ü' as "ue"
+    /// and 'ß' as "ss", so "Müller" sorts as "Mueller".
+    #[default]
+    Phonebook,
+    /// Dictionary order of DIN 5007-1: 'ä' sorts as 'a', 'ö' as 'o', 'ü' as 'u'
+    /// and 'ß' as "ss". Words only differing in umlauts sort the word without them first,
+    /// like "Bar" before "Bär".
+    Dictionary,
+    /// Austrian order of ÖNORM A 1080: 'ä', 'ö' and 'ü' are letters of their own,
+    /// sorting after 'a', 'o' and 'u', so "Azur" sorts before "Ärger". 'ß' sorts as "ss".
+    Austrian,
+}
+
+/// Returns a key that orders byte wise like `bytes` in `style`.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{german_sort_key, SortStyle};
+///
+/// let key = |text: &str| german_sort_key(text.as_bytes(), SortStyle::Phonebook);
+/// assert!(key("Mädler") < key("Mahler"));
+/// assert_eq!(key("Müller"), key("MUELLER"));
+/// ```
+pub fn german_sort_key(bytes: &[u8], style: SortStyle) -> Vec<u8> {
+    let lower = german_lowercase(bytes);
+    let mut key = Vec::with_capacity(lower.len() * 2);
+    match style {
+        SortStyle::Phonebook => {
+            fold_din_5007_2(&lower, &mut key);
+        }
+        SortStyle::Dictionary => {
+            push_letters(&lower, &mut key, |key, base, _| key.push(base));
+            // words only differing in umlauts are ordered by the word itself
+            key.push(0);
+            key.extend_from_slice(&lower);
+        }
+        SortStyle::Austrian => {
+            push_letters(&lower, &mut key, |key, base, umlaut| {
+                key.extend_from_slice(&[base, u8::from(umlaut)])
+            });
+        }
+    }
+    key
+}
+
+/// Appends `lower` to `key` with 'ß' as "ss" and every other byte encoded by `letter`.
+///
+/// `letter` is called with the ASCII base letter of the byte or umlaut
+/// and whether it was an umlaut, bytes other than umlauts are their own base letter.
+fn push_letters<F: Fn(&mut Vec<u8>, u8, bool)>(lower: &[u8], key: &mut Vec<u8>, letter: F) {
+    let mut i = 0;
+    while i < lower.len() {
+        i += match lower[i..] {
+            [0xc3, 0xa4, ..] => {
+                letter(key, b'a', true);
+                2
+            }
+            [0xc3, 0xb6, ..] => {
+                letter(key, b'o', true);
+                2
+            }
+            [0xc3, 0xbc, ..] => {
+                letter(key, b'u', true);
+                2
+            }
+            [0xc3, 0x9f, ..] => {
+                letter(key, b's', false);
+                letter(key, b's', false);
+                2
+            }
+            _ => {
+                letter(key, lower[i], false);
+                1
+            }
+        };
+    }
+}
+
+/// Sorts `items` by the german collation `style` of their `key`.
+///
+/// The sort key of every item is computed once, see [`german_sort_key`].
+/// The sort is stable, so items with equal keys keep their order.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{sort_german, SortStyle};
+///
+/// let mut names = vec!["Müller", "Muff", "Mader", "Mädler"];
+/// sort_german(&mut names, |name| name.as_bytes(), SortStyle::Phonebook);
+/// assert_eq!(names, vec!["Mader", "Mädler", "Müller", "Muff"]);
+/// ```
+pub fn sort_german<T, F>(items: &mut [T], key: F, style: SortStyle)
+where
+    F: Fn(&T) -> &[u8],
+{
+    items.sort_by_cached_key(|item| german_sort_key(key(item), style));
+}
+
+/// Compares `a` and `b` by the german collation `style`.
+///
+/// Words with equal sort keys, like spelling variants, are ordered by their bytes,
+/// so the order is total and can be used with `sort_by` and `dedup_by`.
+/// The sort keys are computed on every call, use [`sort_german`] to sort many items.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{cmp_german, SortStyle};
+///
+/// let mut names = vec!["Mueller", "Muff", "Müller", "MÜLLER"];
+/// names.sort_by(|a, b| cmp_german(a.as_bytes(), b.as_bytes(), SortStyle::Phonebook));
+/// assert_eq!(names, vec!["Mueller", "MÜLLER", "Müller", "Muff"]);
+/// ```
+pub fn cmp_german(a: &[u8], b: &[u8], style: SortStyle) -> Ordering {
+    german_sort_key(a, style)
+        .cmp(&german_sort_key(b, style))
+        .then_with(|| a.cmp(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cmp_german, german_sort_key, sort_german, SortStyle};
+    use std::cmp::Ordering;
+
+    fn sorted<'a>(names: &[&'a str], style: SortStyle) -> Vec<&'a str> {
+        let mut names = names.to_vec();
+        sort_german(&mut names, |name| name.as_bytes(), style);
+        names
+    }
+
+    const NAMES: [&str; 8] = [
+        "Muff", "Müller", "Mader", "Mädler", "Mueck", "Maye", "Mahler", "Mass",
+    ];
+
+    #[test]
+    fn phonebook() {
+        assert_eq!(
+            sorted(&NAMES, SortStyle::Phonebook),
+            vec!["Mader", "Mädler", "Mahler", "Mass", "Maye", "Mueck", "Müller", "Muff"]
+        );
+        assert_eq!(
+            sorted(
+                &["Straße", "Strasse", "STRASSE", "Strand"],
+                SortStyle::Phonebook
+            ),
+            vec!["Strand", "Straße", "Strasse", "STRASSE"]
+        );
+    }
+
+    #[test]
+    fn dictionary() {
+        assert_eq!(
+            sorted(&NAMES, SortStyle::Dictionary),
+            vec!["Mader", "Mädler", "Mahler", "Mass", "Maye", "Mueck", "Muff", "Müller"]
+        );
+        assert_eq!(
+            sorted(&["Bär", "Bar", "Barren", "BAR"], SortStyle::Dictionary),
+            vec!["Bar", "BAR", "Bär", "Barren"]
+        );
+    }
+
+    #[test]
+    fn austrian() {
+        assert_eq!(
+            sorted(&NAMES, SortStyle::Austrian),
+            vec!["Mader", "Mahler", "Mass", "Maye", "Mädler", "Mueck", "Muff", "Müller"]
+        );
+        assert_eq!(
+            sorted(&["Öl", "Ozean", "Pfeife", "Ober"], SortStyle::Austrian),
+            vec!["Ober", "Ozean", "Öl", "Pfeife"]
+        );
+    }
+
+    #[test]
+    fn total_order() {
+        let words = [
+            "Müller", "Mueller", "MÜLLER", "MUELLER", "Muller", "Straße", "Strasse", "STRASSE",
+            "Bär", "Bar", "BAR", "", "Müller",
+        ];
+        let styles = [
+            SortStyle::Phonebook,
+            SortStyle::Dictionary,
+            SortStyle::Austrian,
+        ];
+        for &style in styles.iter() {
+            let cmp = |a: &str, b: &str| cmp_german(a.as_bytes(), b.as_bytes(), style);
+            for a in words.iter() {
+                for b in words.iter() {
+                    // equal only if the bytes are equal, and antisymmetric
+                    assert_eq!(cmp(a, b) == Ordering::Equal, a == b);
+                    assert_eq!(cmp(a, b), cmp(b, a).reverse());
+                    // consistent with the sort key
+                    let keys = german_sort_key(a.as_bytes(), style)
+                        .cmp(&german_sort_key(b.as_bytes(), style));
+                    if keys != Ordering::Equal {
+                        assert_eq!(cmp(a, b), keys);
+                    }
+                    for c in words.iter() {
+                        if cmp(a, b) == Ordering::Less && cmp(b, c) == Ordering::Less {
+                            assert_eq!(cmp(a, c), Ordering::Less);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Umlaut statistics collected while folding.
+use crate::fold::next_umlaut;
+use crate::Umlaut;
+use std::fmt;
+
+/// All umlauts in the order of their discriminants.
+const UMLAUTS: [Umlaut; 8] = [
+    Umlaut::LowercaseA,
+    Umlaut::LowercaseO,
+    Umlaut::LowercaseU,
+    Umlaut::UppercaseA,
+    Umlaut::UppercaseO,
+    Umlaut::UppercaseU,
+    Umlaut::LowercaseSharpS,
+    Umlaut::UppercaseSharpS,
+];
+
+/// Number of occurrences of each umlaut in a text.
+///
+/// Returned by [`UmlautsOwned::fold_with_stats`](crate::UmlautsOwned::fold_with_stats).
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::prelude::*;
+/// use umlauts::Umlaut;
+///
+/// let (_, stats) = "Grüße aus Köln".fold_with_stats();
+/// assert_eq!(stats.count(Umlaut::LowercaseU), 1);
+/// assert_eq!(stats.count(Umlaut::UppercaseA), 0);
+/// assert_eq!(stats.total(), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UmlautStats {
+    counts: [usize; 8],
+}
+
+impl UmlautStats {
+    /// Returns the number of occurrences of `umlaut`.
+    pub fn count(&self, umlaut: Umlaut) -> usize {
+        self.counts[umlaut as usize]
+    }
+
+    /// Returns the number of occurrences of all umlauts.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+/// The set of umlauts occurring in a text.
+///
+/// Returned by [`UmlautsOwned::distinct_umlauts`](crate::UmlautsOwned::distinct_umlauts).
+/// The set is displayed as its umlauts in the order "äöüÄÖÜßẞ".
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::prelude::*;
+/// use umlauts::Umlaut;
+///
+/// let umlauts = "Füße".distinct_umlauts();
+/// assert!(umlauts.contains(Umlaut::LowercaseU));
+/// assert!(!umlauts.contains(Umlaut::LowercaseA));
+/// assert_eq!(umlauts.len(), 2);
+/// assert_eq!(umlauts.to_string(), "üß");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct UmlautSet {
+    bits: u8,
+}
+
+impl UmlautSet {
+    /// Creates an empty set.
+    pub fn new() -> UmlautSet {
+        UmlautSet::default()
+    }
+
+    /// Adds `umlaut` to the set.
+    pub fn insert(&mut self, umlaut: Umlaut) {
+        self.bits |= 1 << umlaut as u8;
+    }
+
+    /// Checks if `umlaut` is in the set.
+    pub fn contains(&self, umlaut: Umlaut) -> bool {
+        self.bits & 1 << umlaut as u8 != 0
+    }
+
+    /// Returns the number of umlauts in the set.
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Checks if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns an iterator over the umlauts in the set in the order "äöüÄÖÜßẞ".
+    pub fn iter(&self) -> UmlautSetIter {
+        UmlautSetIter {
+            set: *self,
+            index: 0,
+        }
+    }
+}
+
+impl fmt::Display for UmlautSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.iter().try_for_each(|umlaut| write!(f, "{}", umlaut))
+    }
+}
+
+impl IntoIterator for UmlautSet {
+    type Item = Umlaut;
+    type IntoIter = UmlautSetIter;
+
+    fn into_iter(self) -> UmlautSetIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the umlauts of an [`UmlautSet`].
+#[derive(Debug, Clone)]
+pub struct UmlautSetIter {
+    set: UmlautSet,
+    index: usize,
+}
+
+impl Iterator for UmlautSetIter {
+    type Item = Umlaut;
+
+    fn next(&mut self) -> Option<Umlaut> {
+        while let Some(&umlaut) = UMLAUTS.get(self.index) {
+            self.index += 1;
+            if self.set.contains(umlaut) {
+                return Some(umlaut);
+            }
+        }
+        None
+    }
+}
+
+/// Collects the umlauts occurring in `bytes`.
+///
+/// See [`UmlautsOwned::distinct_umlauts`](crate::UmlautsOwned::distinct_umlauts).
+pub(crate) fn distinct_umlauts(bytes: &[u8]) -> UmlautSet {
+    let mut set = UmlautSet::new();
+    let mut last = 0;
+    while let Some((range, _)) = next_umlaut(bytes, last) {
+        if let Some(umlaut) = Umlaut::from_bytes(&bytes[range.start..]) {
+            set.insert(umlaut);
+        }
+        if set.len() == UMLAUTS.len() {
+            break;
+        }
+        last = range.end;
+    }
+    set
+}
+
+/// Folds `bytes` according to DIN 5007-2 and counts the replaced umlauts.
+///
+/// See [`UmlautsOwned::fold_with_stats`](crate::UmlautsOwned::fold_with_stats).
+pub(crate) fn fold_with_stats(bytes: &[u8]) -> (Vec<u8>, UmlautStats) {
+    let mut folded = Vec::with_capacity(bytes.len());
+    let mut stats = UmlautStats::default();
+    let mut last = 0;
+    while let Some((range, replacement)) = next_umlaut(bytes, last) {
+        if let Some(umlaut) = Umlaut::from_bytes(&bytes[range.start..]) {
+            stats.counts[umlaut as usize] += 1;
+        }
+        folded.extend_from_slice(&bytes[last..range.start]);
+        folded.extend_from_slice(replacement);
+        last = range.end;
+    }
+    folded.extend_from_slice(&bytes[last..]);
+    (folded, stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{distinct_umlauts, fold_with_stats, UmlautSet, UmlautStats, UMLAUTS};
+    use crate::{Umlaut, UmlautsOwned};
+
+    #[test]
+    fn counts() {
+        let (folded, stats) = fold_with_stats("ÄÖÜäöüßẞ ää ẞ".as_bytes());
+        assert_eq!(folded, b"AeOeUeaeoeuessSS aeae SS");
+        let expected = [
+            (Umlaut::LowercaseA, 3),
+            (Umlaut::LowercaseO, 1),
+            (Umlaut::LowercaseU, 1),
+            (Umlaut::UppercaseA, 1),
+            (Umlaut::UppercaseO, 1),
+            (Umlaut::UppercaseU, 1),
+            (Umlaut::LowercaseSharpS, 1),
+            (Umlaut::UppercaseSharpS, 2),
+        ];
+        for &(umlaut, count) in expected.iter() {
+            assert_eq!(stats.count(umlaut), count, "{:?}", umlaut);
+        }
+        assert_eq!(stats.total(), 11);
+        assert_eq!(fold_with_stats(b"").1, UmlautStats::default());
+    }
+
+    #[test]
+    fn total_matches_count_umlauts() {
+        for text in ["Öl Ärmel Übermut", "GROẞE Straße", "café", ""].iter() {
+            let (folded, stats) = fold_with_stats(text.as_bytes());
+            assert_eq!(stats.total(), text.count_umlauts());
+            assert_eq!(folded, text.fold_ascii_keep_case());
+        }
+    }
+
+    #[test]
+    fn distinct() {
+        let set = distinct_umlauts("Füße".as_bytes());
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Umlaut::LowercaseU, Umlaut::LowercaseSharpS]
+        );
+        assert_eq!(set.to_string(), "üß");
+        assert_eq!(distinct_umlauts("ÜBER über".as_bytes()).len(), 2);
+
+        let all = distinct_umlauts("ẞßÜÖÄüöä äöü".as_bytes());
+        assert_eq!(all.len(), 8);
+        assert_eq!(all.into_iter().collect::<Vec<_>>(), UMLAUTS.to_vec());
+        assert_eq!(all.to_string(), "äöüÄÖÜßẞ");
+
+        let none = distinct_umlauts("Strasse".as_bytes());
+        assert!(none.is_empty());
+        assert_eq!(none, UmlautSet::new());
+        assert_eq!(none.to_string(), "");
+    }
+
+    #[test]
+    fn distinct_matches_stats() {
+        for text in ["Öl Ärmel Übermut", "GROẞE Straße", "café", ""].iter() {
+            let (_, stats) = fold_with_stats(text.as_bytes());
+            let set = distinct_umlauts(text.as_bytes());
+            for &umlaut in UMLAUTS.iter() {
+                assert_eq!(set.contains(umlaut), stats.count(umlaut) > 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Folding of data delivered in chunks.
+use crate::fold::{din_5007_2_at, fold_din_5007_2};
+use crate::Transliterator;
+use std::io::{self, BufRead};
+
+/// State for folding umlauts according to DIN 5007-2 in arbitrary chunks.
+///
+/// Umlauts can be split between two chunks, e.g. when reading from a socket.
+/// `UmlautFoldState` holds the lead bytes of a possibly split umlaut back until
+/// the next chunk (or [`finish`](UmlautFoldState::finish)) decides what they are.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::UmlautFoldState;
+///
+/// let bytes = "Grüße".as_bytes();
+/// let mut state = UmlautFoldState::new();
+/// let mut out = Vec::new();
+/// state.push(&bytes[..3], &mut out);
+/// state.push(&bytes[3..], &mut out);
+/// state.finish(&mut out);
+/// assert_eq!(out, b"Gruesse");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UmlautFoldState {
+    pending: [u8; 2],
+    pending_len: usize,
+}
+
+impl UmlautFoldState {
+    /// Creates a new state without any pending bytes.
+    pub fn new() -> UmlautFoldState {
+        UmlautFoldState::default()
+    }
+
+    /// Folds `input` and appends the result to `out`.
+    ///
+    /// A trailing lead byte of a possibly split umlaut is held back until the next call.
+    pub fn push(&mut self, input: &[u8], out: &mut Vec<u8>) {
+        let mut input = input;
+        while self.pending_len > 0 {
+            let next = match input.first() {
+                Some(&next) => next,
+                None => return,
+            };
+            if self.pending[..self.pending_len] == [0xe1] && next == 0xba {
+                self.pending = [0xe1, 0xba];
+                self.pending_len = 2;
+                input = &input[1..];
+                continue;
+            }
+
+            let mut candidate = [0; 3];
+            candidate[..self.pending_len].copy_from_slice(&self.pending[..self.pending_len]);
+            candidate[self.pending_len] = next;
+            match din_5007_2_at(&candidate[..=self.pending_len]) {
+                Some((_, replacement)) => {
+                    out.extend_from_slice(replacement);
+                    input = &input[1..];
+                }
+                // not an umlaut, `next` is processed on its own
+                None => out.extend_from_slice(&self.pending[..self.pending_len]),
+            }
+            self.pending_len = 0;
+        }
+
+        let held = match input {
+            [.., 0xe1, 0xba] => 2,
+            [.., 0xc3] | [.., 0xe1] => 1,
+            _ => 0,
+        };
+        let (complete, rest) = input.split_at(input.len() - held);
+        fold_din_5007_2(complete, out);
+        self.pending[..held].copy_from_slice(rest);
+        self.pending_len = held;
+    }
+
+    /// Returns the bytes currently held back, waiting for the next chunk.
+    ///
+    /// These are a trailing lead byte (0xc3 or 0xe1) or the first two bytes of a
+    /// possibly split 'ẞ'. They are emitted by the next [`push`](UmlautFoldState::push)
+    /// or by [`finish`](UmlautFoldState::finish), e.g. after a timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::UmlautFoldState;
+    ///
+    /// let mut state = UmlautFoldState::new();
+    /// let mut out = Vec::new();
+    /// state.push(&"ẞ".as_bytes()[..2], &mut out);
+    /// assert_eq!(state.pending_bytes(), &[0xe1, 0xba]);
+    /// state.push(&"ẞ".as_bytes()[2..], &mut out);
+    /// assert!(state.pending_bytes().is_empty());
+    /// assert_eq!(out, b"SS");
+    /// ```
+    pub fn pending_bytes(&self) -> &[u8] {
+        &self.pending[..self.pending_len]
+    }
+
+    /// Appends the bytes that are still held back to `out` unchanged.
+    ///
+    /// Call this after the last chunk, otherwise the held back bytes are lost.
+    pub fn finish(&mut self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.pending[..self.pending_len]);
+        self.pending_len = 0;
+    }
+}
+
+/// Iterator over the folded lines of a reader, created by [`folded_lines`].
+#[derive(Debug)]
+pub struct FoldedLines<R> {
+    reader: R,
+    transliterator: Transliterator,
+    buf: Vec<u8>,
+}
+
+impl<R: BufRead> Iterator for FoldedLines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        self.buf.clear();
+        match self.reader.read_until(b'\n', &mut self.buf) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(err) => return Some(Err(err)),
+        }
+        let line = match self.buf.as_slice() {
+            [line @ .., b'\r', b'\n'] | [line @ .., b'\n'] => line,
+            line => line,
+        };
+        if let Err(err) = std::str::from_utf8(line) {
+            return Some(Err(io::Error::new(io::ErrorKind::InvalidData, err)));
+        }
+        let folded = self.transliterator.fold_ascii(line);
+        // folding keeps valid UTF-8 valid
+        Some(Ok(
+            String::from_utf8(folded).expect("folded line is valid UTF-8")
+        ))
+    }
+}
+
+/// Reads `reader` line by line and folds every line with `transliterator`.
+///
+/// Lines are split at "\n" or "\r\n", which are not part of the yielded lines, like
+/// [`BufRead::lines`]. The line buffer is reused for every line.
+/// Errors of the reader are yielded as they occur, and a line that is not valid UTF-8
+/// is yielded as an error of kind [`InvalidData`](io::ErrorKind::InvalidData).
+/// Reading continues with the next line after an error.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{folded_lines, Transliterator};
+///
+/// let input = "Grüße\r\naus Köln\n".as_bytes();
+/// let lines: Vec<String> = folded_lines(input, Transliterator::new())
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(lines, vec!["Gruesse", "aus Koeln"]);
+/// ```
+pub fn folded_lines<R: BufRead>(reader: R, transliterator: Transliterator) -> FoldedLines<R> {
+    FoldedLines {
+        reader,
+        transliterator,
+        buf: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{folded_lines, UmlautFoldState};
+    use crate::{CasePolicy, Transliterator, UmlautsOwned};
+    use std::io::{self, BufReader, Read};
+
+    const TEXT: &str = "Öl Ärmel Übermut, GROẞE Straße, café ─ ä\u{c3}";
+
+    fn fold_chunked(bytes: &[u8], chunk_size: usize) -> Vec<u8> {
+        let mut state = UmlautFoldState::new();
+        let mut out = Vec::new();
+        for chunk in bytes.chunks(chunk_size) {
+            state.push(chunk, &mut out);
+        }
+        state.finish(&mut out);
+        out
+    }
+
+    #[test]
+    fn whole_buffer() {
+        assert_eq!(
+            fold_chunked(TEXT.as_bytes(), TEXT.len()),
+            "Oel Aermel Uebermut, GROSSE Strasse, café ─ ae\u{c3}".as_bytes()
+        );
+    }
+
+    #[test]
+    fn byte_by_byte() {
+        let bytes = TEXT.as_bytes();
+        assert_eq!(fold_chunked(bytes, 1), fold_chunked(bytes, bytes.len()));
+        let folded = fold_chunked("ÄÖÜäöüßẞ".as_bytes(), 1);
+        assert_eq!(folded.ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn all_chunk_sizes() {
+        let bytes = TEXT.as_bytes();
+        let expected = fold_chunked(bytes, bytes.len());
+        for chunk_size in 1..bytes.len() {
+            assert_eq!(fold_chunked(bytes, chunk_size), expected);
+        }
+    }
+
+    #[test]
+    fn pending_bytes() {
+        let mut state = UmlautFoldState::new();
+        let mut out = Vec::new();
+        assert_eq!(state.pending_bytes(), b"");
+        state.push(&[b'a', 0xc3], &mut out);
+        assert_eq!(state.pending_bytes(), [0xc3]);
+        state.push(&[], &mut out);
+        assert_eq!(state.pending_bytes(), [0xc3]);
+        state.push(&[0xbc, 0xe1], &mut out);
+        assert_eq!(state.pending_bytes(), [0xe1]);
+        state.push(&[0xba], &mut out);
+        assert_eq!(state.pending_bytes(), [0xe1, 0xba]);
+        state.push(b"x", &mut out);
+        assert_eq!(state.pending_bytes(), b"");
+        state.push(&[0xc3], &mut out);
+        state.finish(&mut out);
+        assert_eq!(state.pending_bytes(), b"");
+        assert_eq!(out, [b'a', b'u', b'e', 0xe1, 0xba, b'x', 0xc3]);
+    }
+
+    #[test]
+    fn split_lead_bytes() {
+        let mut state = UmlautFoldState::new();
+        let mut out = Vec::new();
+        state.push(&[b'a', 0xe1], &mut out);
+        assert_eq!(out, b"a");
+        state.push(&[0xba], &mut out);
+        assert_eq!(out, b"a");
+        state.push(b"b", &mut out);
+        assert_eq!(out, [b'a', 0xe1, 0xba, b'b']);
+        state.push(&[0xc3], &mut out);
+        state.push(&[], &mut out);
+        state.push(&[0xc3, 0xa4], &mut out);
+        state.finish(&mut out);
+        assert_eq!(out, [b'a', 0xe1, 0xba, b'b', 0xc3, b'a', b'e']);
+    }
+
+    #[test]
+    fn lines() {
+        let input = "Öl\r\nÄrmel\n\nGROẞE Straße\r\r\nÜbermut";
+        let lines: Vec<String> = folded_lines(input.as_bytes(), Transliterator::new())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            lines,
+            vec!["Oel", "Aermel", "", "GROSSE Strasse\r", "Uebermut"]
+        );
+
+        let upper = Transliterator::new().case(CasePolicy::Upper);
+        // a tiny buffer, so lines span several reads
+        let reader = BufReader::with_capacity(2, "grüße\nköln\n".as_bytes());
+        let lines: Vec<String> = folded_lines(reader, upper).map(Result::unwrap).collect();
+        assert_eq!(lines, vec!["GRUESSE", "KOELN"]);
+        assert_eq!(folded_lines(&b""[..], Transliterator::new()).count(), 0);
+    }
+
+    #[test]
+    fn line_errors() {
+        let input = [b'a', b'\n', 0xc3, b'\n', b'b'];
+        let mut lines = folded_lines(&input[..], Transliterator::new());
+        assert_eq!(lines.next().unwrap().unwrap(), "a");
+        let err = lines.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(lines.next().unwrap().unwrap(), "b");
+        assert!(lines.next().is_none());
+
+        struct Failing;
+        impl Read for Failing {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken pipe"))
+            }
+        }
+        let mut lines = folded_lines(BufReader::new(Failing), Transliterator::new());
+        assert_eq!(
+            lines.next().unwrap().unwrap_err().to_string(),
+            "broken pipe"
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::fold::next_umlaut;
+use crate::UmlautsOwned;
+use std::borrow::Cow;
+
+/// String processing functions for `str` that borrow if nothing changes.
+///
+/// `UmlautsStrExt` adds functions for the german "Umlauts" that return a [`Cow`],
+/// so text without umlauts is returned without allocating.
+/// Unlike the `str` implementation of [`UmlautsInplaceExt`](crate::UmlautsInplaceExt),
+/// this needs no `unsafe` feature.
+pub trait UmlautsStrExt {
+    /// Converts Umlauts to ae, oe, ue, ss, ... borrowing text without umlauts.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`fold_ascii_string`](crate::UmlautsOwned::fold_ascii_string), but returns
+    /// `Cow::Borrowed` with the unchanged text if it contains no umlaut.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    /// use std::borrow::Cow;
+    ///
+    /// assert_eq!("Grüße".umlauts_to_ascii_cow(), "Gruesse");
+    /// assert!(matches!("Gruesse".umlauts_to_ascii_cow(), Cow::Borrowed(_)));
+    /// ```
+    fn umlauts_to_ascii_cow(&self) -> Cow<'_, str>;
+}
+
+impl UmlautsStrExt for str {
+    fn umlauts_to_ascii_cow(&self) -> Cow<'_, str> {
+        match next_umlaut(self.as_bytes(), 0) {
+            Some(_) => Cow::Owned(self.fold_ascii_string()),
+            None => Cow::Borrowed(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::UmlautsStrExt;
+    use std::borrow::Cow;
+
+    #[test]
+    fn umlauts_to_ascii_cow_borrows() {
+        let text = "Gruesse aus Koeln, café";
+        match text.umlauts_to_ascii_cow() {
+            Cow::Borrowed(folded) => assert_eq!(folded.as_ptr(), text.as_ptr()),
+            Cow::Owned(_) => panic!("text without umlauts was copied"),
+        }
+        assert!(matches!("".umlauts_to_ascii_cow(), Cow::Borrowed("")));
+    }
+
+    #[test]
+    fn umlauts_to_ascii_cow_allocates() {
+        match "GROẞE Grüße".umlauts_to_ascii_cow() {
+            Cow::Owned(folded) => assert_eq!(folded, "GROSSE Gruesse"),
+            Cow::Borrowed(_) => panic!("umlauts were not folded"),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! ASCII replacements for symbols commonly found next to german text.
+
+/// Returns the byte length and the ASCII replacement of the symbol at the start of `bytes`.
+///
+/// Used by [`UmlautsOwned::fold_ascii_strict`](crate::UmlautsOwned::fold_ascii_strict)
+/// if the `symbols` feature is enabled:
+/// - '°' -> "deg"
+/// - 'µ' -> 'u'
+/// - the german quotation marks '„', '“', '”', '»' and '«' -> '"'
+/// - the single quotation marks '‚', '‘', '’', '›' and '‹' -> '\''
+/// - the fullwidth latin letters 'Ａ' to 'Ｚ' and 'ａ' to 'ｚ' -> 'A' to 'Z' and 'a' to 'z'
+///
+/// The quotation marks are typographic helpers for legacy systems, not german letters.
+pub(crate) fn symbol_at(bytes: &[u8]) -> Option<(usize, &'static [u8])> {
+    match bytes {
+        // °
+        [0xc2, 0xb0, ..] => Some((2, b"deg")),
+        // µ (MICRO SIGN)
+        [0xc2, 0xb5, ..] => Some((2, b"u")),
+        // » «
+        [0xc2, 0xbb, ..] | [0xc2, 0xab, ..] => Some((2, b"\"")),
+        // › ‹
+        [0xe2, 0x80, 0xba, ..] | [0xe2, 0x80, 0xb9, ..] => Some((3, b"'")),
+        // „ “ ”
+        [0xe2, 0x80, 0x9e, ..] | [0xe2, 0x80, 0x9c, ..] | [0xe2, 0x80, 0x9d, ..] => {
+            Some((3, b"\""))
+        }
+        // ‚ ‘ ’
+        [0xe2, 0x80, 0x9a, ..] | [0xe2, 0x80, 0x98, ..] | [0xe2, 0x80, 0x99, ..] => Some((3, b"'")),
+        // Ａ to Ｚ (U+FF21 to U+FF3A)
+        [0xef, 0xbc, byte @ 0xa1..=0xba, ..] => Some((3, ascii_letter(UPPERCASE, byte - 0xa1))),
+        // ａ to ｚ (U+FF41 to U+FF5A)
+        [0xef, 0xbd, byte @ 0x81..=0x9a, ..] => Some((3, ascii_letter(LOWERCASE, byte - 0x81))),
+        _ => None,
+    }
+}
+
+const UPPERCASE: &[u8; 26] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWERCASE: &[u8; 26] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// Returns the letter at `index` of `alphabet` as a slice.
+fn ascii_letter(alphabet: &'static [u8; 26], index: u8) -> &'static [u8] {
+    let index = usize::from(index);
+    &alphabet[index..=index]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::symbol_at;
+    use crate::UmlautsOwned;
+
+    #[test]
+    fn symbols() {
+        assert_eq!(symbol_at("°C".as_bytes()), Some((2, &b"deg"[..])));
+        assert_eq!(symbol_at("µm".as_bytes()), Some((2, &b"u"[..])));
+        assert_eq!(symbol_at("ä".as_bytes()), None);
+        assert_eq!(symbol_at(&[0xc2]), None);
+        assert_eq!(symbol_at(&[0xe2, 0x80]), None);
+        assert_eq!(symbol_at("…".as_bytes()), None);
+    }
+
+    #[test]
+    fn strict_fold() {
+        assert_eq!(
+            "Bei 20 °C ist Süßwasser 5 µm tief".fold_ascii_strict(),
+            Ok(b"Bei 20 degC ist Suesswasser 5 um tief".to_vec())
+        );
+        assert_eq!("20 ©".fold_ascii_strict(), Err(vec![(3, '©')]));
+    }
+
+    #[test]
+    fn fullwidth_letters() {
+        assert_eq!(symbol_at("Ａ".as_bytes()), Some((3, &b"A"[..])));
+        assert_eq!(symbol_at("Ｚ".as_bytes()), Some((3, &b"Z"[..])));
+        assert_eq!(symbol_at("ａ".as_bytes()), Some((3, &b"a"[..])));
+        assert_eq!(symbol_at("ｚ".as_bytes()), Some((3, &b"z"[..])));
+        // fullwidth digits and punctuation are not letters
+        assert_eq!(symbol_at("１".as_bytes()), None);
+        assert_eq!(symbol_at("［".as_bytes()), None);
+        assert_eq!(
+            "Ｍüｌｌｅｒ ＧｍｂＨ".fold_ascii_strict(),
+            Ok(b"Mueller GmbH".to_vec())
+        );
+    }
+
+    #[test]
+    fn quotation_marks() {
+        assert_eq!(
+            "„Grüß Gott“, sagte er. »Schön« ‚hier’ ›drüben‹".fold_ascii_strict(),
+            Ok(b"\"Gruess Gott\", sagte er. \"Schoen\" 'hier' 'drueben'".to_vec())
+        );
+        assert_eq!("”“".fold_ascii_strict(), Ok(b"\"\"".to_vec()));
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers shared by the tests.
+
+/// A xorshift generator for random test input that is the same in every run.
+pub(crate) struct Xorshift {
+    state: u32,
+}
+
+impl Xorshift {
+    /// Creates a generator starting at the non zero `seed`.
+    pub(crate) fn new(seed: u32) -> Xorshift {
+        Xorshift { state: seed }
+    }
+
+    /// Returns a random item of `items`.
+    pub(crate) fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        &items[self.state as usize % items.len()]
+    }
+}
@@ -0,0 +1,157 @@
+//! Traditional orthography from before the 1996 reform.
+use crate::case::capitalize_first;
+use crate::utf8::decode_char;
+
+/// Common words written with 'ss' since the 1996 reform and with 'ß' before.
+///
+/// Pairs of the reformed and the traditional spelling, in lowercase.
+/// Extend it by passing your own list to [`traditional_sharp_s`].
+pub const TRADITIONAL_SHARP_S_WORDS: &[(&str, &str)] = &[
+    ("dass", "daß"),
+    ("muss", "muß"),
+    ("musst", "mußt"),
+    ("müsst", "müßt"),
+    ("wusste", "wußte"),
+    ("wussten", "wußten"),
+    ("gewusst", "gewußt"),
+    ("lässt", "läßt"),
+    ("misst", "mißt"),
+    ("isst", "ißt"),
+    ("bisschen", "bißchen"),
+    ("hässlich", "häßlich"),
+    ("fluss", "fluß"),
+    ("kuss", "kuß"),
+    ("nuss", "nuß"),
+    ("schluss", "schluß"),
+    ("schloss", "schloß"),
+    ("genuss", "genuß"),
+    ("fass", "faß"),
+    ("hass", "haß"),
+    ("biss", "biß"),
+    ("riss", "riß"),
+    ("prozess", "prozeß"),
+    ("stress", "streß"),
+];
+
+/// Returns the length of the word at the start of `bytes`, made of alphabetic characters.
+///
+/// Punctuation like '„' or '—' and spaces like the no-break space end a word.
+fn word_len(bytes: &[u8]) -> usize {
+    let mut len = 0;
+    while let Some((c, char_len)) = decode_char(&bytes[len..]) {
+        if !c.is_alphabetic() {
+            break;
+        }
+        len += char_len;
+    }
+    len
+}
+
+/// Rewrites every word found in `words` to its traditional spelling.
+///
+/// `words` contains pairs of the reformed and the traditional spelling in lowercase,
+/// like [`TRADITIONAL_SHARP_S_WORDS`].
+/// Words are matched as a whole, either in lowercase or with a capitalized first letter,
+/// which is kept. Words in all caps are left unchanged, as 'ß' was expanded to 'SS' there.
+///
+/// Requires the `traditional` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{traditional_sharp_s, TRADITIONAL_SHARP_S_WORDS};
+///
+/// let text = "Ich weiß, dass der Fluss fließt.".as_bytes();
+/// assert_eq!(traditional_sharp_s(text, TRADITIONAL_SHARP_S_WORDS),
+///     "Ich weiß, daß der Fluß fließt.".as_bytes());
+/// ```
+pub fn traditional_sharp_s(bytes: &[u8], words: &[(&str, &str)]) -> Vec<u8> {
+    let mut traditional = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let len = word_len(&bytes[i..]);
+        if len == 0 {
+            // invalid bytes are copied one by one
+            let char_len = decode_char(&bytes[i..]).map_or(1, |(_, char_len)| char_len);
+            traditional.extend_from_slice(&bytes[i..i + char_len]);
+            i += char_len;
+            continue;
+        }
+
+        let word = &bytes[i..i + len];
+        let capitalized = word[0].is_ascii_uppercase();
+        let replacement = words.iter().find_map(|(reformed, old)| {
+            let reformed = reformed.as_bytes();
+            let matches = reformed.len() == word.len()
+                && reformed[0] == word[0].to_ascii_lowercase()
+                && reformed[1..] == word[1..];
+            if matches {
+                Some(old.as_bytes())
+            } else {
+                None
+            }
+        });
+        match replacement {
+            Some(old) if capitalized => traditional.extend_from_slice(&capitalize_first(old)),
+            Some(old) => traditional.extend_from_slice(old),
+            None => traditional.extend_from_slice(word),
+        }
+        i += len;
+    }
+    traditional
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{traditional_sharp_s, TRADITIONAL_SHARP_S_WORDS};
+
+    fn traditional(text: &str) -> String {
+        String::from_utf8(traditional_sharp_s(
+            text.as_bytes(),
+            TRADITIONAL_SHARP_S_WORDS,
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn words() {
+        assert_eq!(traditional("dass"), "daß");
+        assert_eq!(traditional("Dass es so ist"), "Daß es so ist");
+        assert_eq!(
+            traditional("Er lässt den Schlüssel"),
+            "Er läßt den Schlüssel"
+        );
+        assert_eq!(traditional("ein bisschen Stress!"), "ein bißchen Streß!");
+        assert_eq!(
+            traditional("Er sagte, „dass es geht“"),
+            "Er sagte, „daß es geht“"
+        );
+        assert_eq!(
+            traditional("»Fluss«, dass—\u{a0}muss"),
+            "»Fluß«, daß—\u{a0}muß"
+        );
+    }
+
+    #[test]
+    fn whole_words_only() {
+        assert_eq!(traditional("Flüsse Dasselbe"), "Flüsse Dasselbe");
+        assert_eq!(traditional("Flussufer"), "Flussufer");
+        assert_eq!(traditional("DASS"), "DASS");
+        assert_eq!(traditional("dAss"), "dAss");
+        assert_eq!(traditional(""), "");
+        let text = traditional_sharp_s(
+            &[0xff, b'd', b'a', b's', b's', 0xc3],
+            TRADITIONAL_SHARP_S_WORDS,
+        );
+        assert_eq!(text, [&[0xff][..], "daß".as_bytes(), &[0xc3]].concat());
+    }
+
+    #[test]
+    fn custom_words() {
+        let mut words = TRADITIONAL_SHARP_S_WORDS.to_vec();
+        words.push(("flussufer", "flußufer"));
+        let text = traditional_sharp_s("Am Flussufer".as_bytes(), &words);
+        assert_eq!(text, "Am Flußufer".as_bytes());
+    }
+}
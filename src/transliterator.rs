@@ -0,0 +1,466 @@
+//! Configurable allocating transliteration.
+use crate::fold::din_5007_2_at;
+use crate::utf8::{decode_char, decode_last_char};
+
+/// How 'ß' and 'ẞ' are written by [`Transliterator::uppercase`].
+///
+/// Lowercasing always turns 'ẞ' into 'ß'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SharpSPolicy {
+    /// 'ß' and 'ẞ' are expanded to 'SS'.
+    #[default]
+    Expand,
+    /// 'ß' becomes the capital sharp S 'ẞ' as allowed since the 2017 orthography reform,
+    /// 'ẞ' stays 'ẞ'.
+    KeepCapital,
+    /// 'ß' and 'ẞ' are left unchanged.
+    Keep,
+}
+
+/// How ASCII letters are cased by [`Transliterator::fold_ascii`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CasePolicy {
+    /// The case is kept, umlauts are expanded like "Ä" -> "Ae".
+    #[default]
+    Preserve,
+    /// All ASCII letters are lowercased, including the expansions of umlauts.
+    Lower,
+    /// All ASCII letters are uppercased, including the expansions of umlauts.
+    Upper,
+}
+
+/// How 'Ä', 'Ö' and 'Ü' are expanded by [`Transliterator::fold_ascii`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CapitalExpansion {
+    /// Always "Ae", "Oe" and "Ue" as in DIN 5007-2.
+    #[default]
+    Title,
+    /// "AE", "OE" and "UE" in uppercase words like "ÄPFEL", "Ae", "Oe" and "Ue" otherwise.
+    ///
+    /// An umlaut is in an uppercase word if the following letter is uppercase,
+    /// or if no letter follows and the preceding letter is uppercase, like in "BÄ".
+    /// 'ß' has no uppercase form in most texts and is skipped, so "GRÜßE" is uppercase.
+    FollowContext,
+    /// Always "AE", "OE" and "UE", e.g. for acronyms like "ÖPNV" -> "OEPNV".
+    ///
+    /// The lowercase umlauts are still expanded to "ae", "oe" and "ue".
+    Upper,
+}
+
+/// How 'ß' and 'ẞ' are expanded by [`Transliterator::fold_ascii`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SharpSCase {
+    /// 'ß' and 'ẞ' are expanded to "ss".
+    AlwaysLower,
+    /// 'ß' is expanded to "SS" in uppercase words like "GROß", to "ss" otherwise.
+    /// 'ẞ' is always expanded to "SS".
+    ///
+    /// Uppercase words are detected like for [`CapitalExpansion::FollowContext`],
+    /// [`sharp_s_expansion`](crate::sharp_s_expansion) applies the same rule to single bytes.
+    #[default]
+    FollowContext,
+    /// 'ß' and 'ẞ' are expanded to "SS".
+    AlwaysUpper,
+}
+
+impl CasePolicy {
+    /// Applies the policy to the ASCII letters in `bytes`.
+    fn apply(self, bytes: &mut [u8]) {
+        match self {
+            CasePolicy::Preserve => {}
+            CasePolicy::Lower => bytes.make_ascii_lowercase(),
+            CasePolicy::Upper => bytes.make_ascii_uppercase(),
+        }
+    }
+}
+
+/// Builder for allocating transliterations of german umlauts.
+///
+/// In contrast to the in place functions of [`UmlautsInplaceExt`](crate::UmlautsInplaceExt)
+/// the output may differ in length from the input, which allows handling 'ß' and 'ẞ' properly.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::{SharpSPolicy, Transliterator};
+///
+/// let transliterator = Transliterator::new().sharp_s(SharpSPolicy::KeepCapital);
+/// assert_eq!(transliterator.uppercase("Straße".as_bytes()), "STRAẞE".as_bytes());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transliterator {
+    sharp_s: SharpSPolicy,
+    case: CasePolicy,
+    capital_expansion: CapitalExpansion,
+    sharp_s_case: SharpSCase,
+}
+
+impl Transliterator {
+    /// Creates a transliterator with the default settings.
+    pub fn new() -> Transliterator {
+        Transliterator::default()
+    }
+
+    /// Sets how 'ß' and 'ẞ' are uppercased, defaults to [`SharpSPolicy::Expand`].
+    pub fn sharp_s(mut self, policy: SharpSPolicy) -> Transliterator {
+        self.sharp_s = policy;
+        self
+    }
+
+    /// Sets how ASCII letters are cased while folding, defaults to [`CasePolicy::Preserve`].
+    pub fn case(mut self, policy: CasePolicy) -> Transliterator {
+        self.case = policy;
+        self
+    }
+
+    /// Sets how 'Ä', 'Ö' and 'Ü' are expanded while folding,
+    /// defaults to [`CapitalExpansion::Title`].
+    pub fn capital_expansion(mut self, expansion: CapitalExpansion) -> Transliterator {
+        self.capital_expansion = expansion;
+        self
+    }
+
+    /// Sets how 'ß' and 'ẞ' are expanded while folding,
+    /// defaults to [`SharpSCase::FollowContext`].
+    pub fn sharp_s_case(mut self, case: SharpSCase) -> Transliterator {
+        self.sharp_s_case = case;
+        self
+    }
+
+    /// Uppercases alphabetic ASCII chars and UTF-8 umlauts.
+    ///
+    /// - 'ä' -> 'Ä'
+    /// - 'ö' -> 'Ö'
+    /// - 'ü' -> 'Ü'
+    /// - 'ß' and 'ẞ' according to the [`SharpSPolicy`]
+    ///
+    /// All other bytes are left unchanged.
+    pub fn uppercase(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut upper = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            i += match bytes[i..] {
+                [0xc3, 0xa4, ..] => push(&mut upper, "Ä".as_bytes(), 2),
+                [0xc3, 0xb6, ..] => push(&mut upper, "Ö".as_bytes(), 2),
+                [0xc3, 0xbc, ..] => push(&mut upper, "Ü".as_bytes(), 2),
+                [0xc3, 0x9f, ..] => match self.sharp_s {
+                    SharpSPolicy::Expand => push(&mut upper, b"SS", 2),
+                    SharpSPolicy::KeepCapital => push(&mut upper, "ẞ".as_bytes(), 2),
+                    SharpSPolicy::Keep => push(&mut upper, "ß".as_bytes(), 2),
+                },
+                [0xe1, 0xba, 0x9e, ..] => match self.sharp_s {
+                    SharpSPolicy::Expand => push(&mut upper, b"SS", 3),
+                    SharpSPolicy::KeepCapital | SharpSPolicy::Keep => {
+                        push(&mut upper, "ẞ".as_bytes(), 3)
+                    }
+                },
+                _ => push(&mut upper, &[bytes[i].to_ascii_uppercase()], 1),
+            };
+        }
+        upper
+    }
+
+    /// Converts Umlauts to ae, oe, ue, ss, ...
+    ///
+    /// Maps umlauts according to DIN 5007-2.
+    /// 'Ä', 'Ö' and 'Ü' are expanded according to the [`CapitalExpansion`],
+    /// 'ß' and 'ẞ' according to the [`SharpSCase`].
+    /// Afterwards, ASCII letters are cased according to the [`CasePolicy`].
+    ///
+    /// With the `latin` feature, the ligature 'æ' and the y with diaeresis are folded as well,
+    /// although they are beyond the german alphabet. They are common in borrowed words and
+    /// french or scandinavian names:
+    /// - 'æ' -> "ae"
+    /// - 'Æ' -> "Ae", expanded according to the [`CapitalExpansion`] like 'Ä'
+    /// - 'ÿ' -> 'y'
+    /// - 'Ÿ' -> 'Y'
+    ///
+    /// All other bytes are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::{CasePolicy, Transliterator};
+    ///
+    /// assert_eq!(Transliterator::new().fold_ascii("Grüße".as_bytes()), b"Gruesse");
+    ///
+    /// let lower = Transliterator::new().case(CasePolicy::Lower);
+    /// assert_eq!(lower.fold_ascii("Grüße".as_bytes()), b"gruesse");
+    /// ```
+    pub fn fold_ascii(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut folded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let replacement = din_5007_2_at(&bytes[i..]);
+            #[cfg(feature = "latin")]
+            let replacement = replacement.or_else(|| crate::latin::latin_at(bytes, i));
+            i += match replacement {
+                Some((len, &[b's', b's'])) | Some((len, &[b'S', b'S'])) => {
+                    let upper = match self.sharp_s_case {
+                        SharpSCase::AlwaysLower => false,
+                        SharpSCase::FollowContext => {
+                            len == 3 || in_uppercase_word(bytes, i, i + len)
+                        }
+                        SharpSCase::AlwaysUpper => true,
+                    };
+                    push(&mut folded, if upper { b"SS" } else { b"ss" }, len)
+                }
+                Some((len, &[first, b'e']))
+                    if first.is_ascii_uppercase()
+                        && match self.capital_expansion {
+                            CapitalExpansion::Title => false,
+                            CapitalExpansion::FollowContext => in_uppercase_word(bytes, i, i + len),
+                            CapitalExpansion::Upper => true,
+                        } =>
+                {
+                    push(&mut folded, &[first, b'E'], len)
+                }
+                Some((len, replacement)) => push(&mut folded, replacement, len),
+                None => push(&mut folded, &[bytes[i]], 1),
+            };
+        }
+        self.case.apply(&mut folded);
+        folded
+    }
+
+    /// Lowercases alphabetic ASCII chars and UTF-8 umlauts.
+    ///
+    /// - 'Ä' -> 'ä'
+    /// - 'Ö' -> 'ö'
+    /// - 'Ü' -> 'ü'
+    /// - 'ẞ' -> 'ß'
+    ///
+    /// All other bytes are left unchanged.
+    pub fn lowercase(&self, bytes: &[u8]) -> Vec<u8> {
+        let mut lower = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            i += match bytes[i..] {
+                [0xc3, 0x84, ..] => push(&mut lower, "ä".as_bytes(), 2),
+                [0xc3, 0x96, ..] => push(&mut lower, "ö".as_bytes(), 2),
+                [0xc3, 0x9c, ..] => push(&mut lower, "ü".as_bytes(), 2),
+                [0xe1, 0xba, 0x9e, ..] => push(&mut lower, "ß".as_bytes(), 3),
+                _ => push(&mut lower, &[bytes[i].to_ascii_lowercase()], 1),
+            };
+        }
+        lower
+    }
+}
+
+/// Checks if the character at `bytes[start..end]` is part of an uppercase word.
+///
+/// See [`CapitalExpansion::FollowContext`].
+pub(crate) fn in_uppercase_word(bytes: &[u8], start: usize, end: usize) -> bool {
+    is_uppercase_context(
+        decode_last_char(&bytes[..start]),
+        decode_char(&bytes[end..]).map(|(next, _)| next),
+    )
+}
+
+/// Checks if a character between `prev` and `next` is part of an uppercase word.
+///
+/// The next letter decides, or the previous one if no letter follows.
+/// 'ß' has no uppercase form in most texts, so it isn't considered a letter.
+pub(crate) fn is_uppercase_context(prev: Option<char>, next: Option<char>) -> bool {
+    let is_cased = |c: char| c.is_alphabetic() && c != 'ß';
+    match next {
+        Some(next) if is_cased(next) => next.is_uppercase(),
+        _ => prev.is_some_and(|prev| is_cased(prev) && prev.is_uppercase()),
+    }
+}
+
+/// Appends `replacement` to `out` and returns the number of consumed input bytes.
+fn push(out: &mut Vec<u8>, replacement: &[u8], consumed: usize) -> usize {
+    out.extend_from_slice(replacement);
+    consumed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CapitalExpansion, CasePolicy, SharpSCase, SharpSPolicy, Transliterator};
+    use crate::UmlautsOwned;
+
+    #[test]
+    fn sharp_s_cases() {
+        let text = "GROßE Straße, GROẞE Maẞ".as_bytes();
+        let fold = |case| {
+            let folded = Transliterator::new().sharp_s_case(case).fold_ascii(text);
+            String::from_utf8(folded).unwrap()
+        };
+        assert_eq!(fold(SharpSCase::AlwaysLower), "GROssE Strasse, GROssE Mass");
+        assert_eq!(
+            fold(SharpSCase::FollowContext),
+            "GROSSE Strasse, GROSSE MaSS"
+        );
+        assert_eq!(fold(SharpSCase::AlwaysUpper), "GROSSE StraSSe, GROSSE MaSS");
+        let fold = |text: &str| Transliterator::new().fold_ascii(text.as_bytes());
+        assert_eq!(fold("GROß"), b"GROSS");
+        assert_eq!(fold("Maß"), b"Mass");
+        assert_eq!(fold("ß"), b"ss");
+        assert_eq!(fold("GRÜßE"), b"GRUeSSE");
+    }
+
+    #[test]
+    fn lowercase_capital_sharp_s() {
+        let lower = Transliterator::new().lowercase("STRAẞE".as_bytes());
+        assert_eq!(lower, "straße".as_bytes());
+        let lower = Transliterator::new().lowercase("ẞ".as_bytes());
+        assert_eq!(lower, "ß".as_bytes());
+    }
+
+    #[test]
+    fn lowercase_umlauts() {
+        let lower = Transliterator::new().lowercase("ÖL ÄRMEL ÜBERMUT CAFÉ".as_bytes());
+        assert_eq!(lower, "öl ärmel übermut cafÉ".as_bytes());
+        assert_eq!(Transliterator::new().lowercase(&[0xe1, 0xba]), [0xe1, 0xba]);
+    }
+
+    #[test]
+    fn uppercase_sharp_s_policies() {
+        let text = "weiße straße".as_bytes();
+        let upper = |policy| Transliterator::new().sharp_s(policy).uppercase(text);
+        assert_eq!(upper(SharpSPolicy::Expand), b"WEISSE STRASSE");
+        assert_eq!(upper(SharpSPolicy::KeepCapital), "WEIẞE STRAẞE".as_bytes());
+        assert_eq!(upper(SharpSPolicy::Keep), "WEIßE STRAßE".as_bytes());
+        assert_eq!(Transliterator::new().uppercase(text), b"WEISSE STRASSE");
+    }
+
+    #[test]
+    fn uppercase_capital_sharp_s() {
+        let text = "GROẞ".as_bytes();
+        let upper = |policy| Transliterator::new().sharp_s(policy).uppercase(text);
+        assert_eq!(upper(SharpSPolicy::Expand), b"GROSS");
+        assert_eq!(upper(SharpSPolicy::KeepCapital), "GROẞ".as_bytes());
+        assert_eq!(upper(SharpSPolicy::Keep), "GROẞ".as_bytes());
+    }
+
+    #[test]
+    fn fold_ascii() {
+        let folded = Transliterator::new().fold_ascii("GROẞE Grüße, Café".as_bytes());
+        assert_eq!(folded, "GROSSE Gruesse, Café".as_bytes());
+        assert_eq!(
+            Transliterator::new().fold_ascii(&[b'a', 0xc3]),
+            [b'a', 0xc3]
+        );
+
+        for &case in &[CasePolicy::Preserve, CasePolicy::Lower, CasePolicy::Upper] {
+            for &expansion in &[
+                CapitalExpansion::Title,
+                CapitalExpansion::FollowContext,
+                CapitalExpansion::Upper,
+            ] {
+                for &sharp_s in &[
+                    SharpSCase::AlwaysLower,
+                    SharpSCase::FollowContext,
+                    SharpSCase::AlwaysUpper,
+                ] {
+                    let transliterator = Transliterator::new()
+                        .case(case)
+                        .capital_expansion(expansion)
+                        .sharp_s_case(sharp_s);
+                    let folded = transliterator.fold_ascii("ÄÖÜäöüßẞ, GROẞE Grüße".as_bytes());
+                    assert_eq!(folded.ensure_folded_ascii(), Ok(()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fold_ascii_case_policies() {
+        let text = "Äpfel, GRÜßE und ẞ, Café".as_bytes();
+        let fold = |case| Transliterator::new().case(case).fold_ascii(text);
+        assert_eq!(
+            fold(CasePolicy::Preserve),
+            "Aepfel, GRUeSSE und SS, Café".as_bytes()
+        );
+        assert_eq!(
+            fold(CasePolicy::Lower),
+            "aepfel, gruesse und ss, café".as_bytes()
+        );
+        assert_eq!(
+            fold(CasePolicy::Upper),
+            "AEPFEL, GRUESSE UND SS, CAFé".as_bytes()
+        );
+        for &sharp_s in &[
+            SharpSPolicy::Expand,
+            SharpSPolicy::KeepCapital,
+            SharpSPolicy::Keep,
+        ] {
+            let transliterator = Transliterator::new()
+                .sharp_s(sharp_s)
+                .case(CasePolicy::Lower);
+            assert_eq!(transliterator.fold_ascii("ẞ".as_bytes()), b"ss");
+        }
+    }
+
+    #[test]
+    fn fold_ascii_capital_expansion() {
+        let fold = |expansion, text: &str| {
+            let transliterator = Transliterator::new().capital_expansion(expansion);
+            String::from_utf8(transliterator.fold_ascii(text.as_bytes())).unwrap()
+        };
+        let context = CapitalExpansion::FollowContext;
+        assert_eq!(fold(context, "ÄPFEL"), "AEPFEL");
+        assert_eq!(fold(context, "Äpfel"), "Aepfel");
+        assert_eq!(fold(context, "GRÜNE AUS MÜNCHEN"), "GRUENE AUS MUENCHEN");
+        assert!(fold(context, "GRÜßE").starts_with("GRUE"));
+        assert_eq!(fold(context, "BÄ, Ä, Ö-Üb"), "BAE, Ae, Oe-Ueb");
+        assert_eq!(fold(context, "ÄÖÜ"), "AEOEUE");
+        assert_eq!(fold(context, "äPFEL"), "aePFEL");
+        assert_eq!(fold(CapitalExpansion::Title, "ÄPFEL"), "AePFEL");
+        let upper = CapitalExpansion::Upper;
+        assert_eq!(fold(upper, "ÖPNV"), "OEPNV");
+        assert_eq!(fold(upper, "Äpfel, Öl, Übermut"), "AEpfel, OEl, UEbermut");
+        assert_eq!(fold(upper, "äöü ß"), "aeoeue ss");
+        assert_eq!(fold(CapitalExpansion::Title, "ÖPNV"), "OePNV");
+        assert_eq!(
+            Transliterator::new().fold_ascii("ÄPFEL".as_bytes()),
+            b"AePFEL"
+        );
+    }
+
+    #[cfg(feature = "latin")]
+    #[test]
+    fn fold_ascii_latin() {
+        let fold = |expansion, text: &str| {
+            let transliterator = Transliterator::new().capital_expansion(expansion);
+            String::from_utf8(transliterator.fold_ascii(text.as_bytes())).unwrap()
+        };
+        let text = "Ærø, Cæsar, ÆRØ, ÆÖ";
+        assert_eq!(
+            fold(CapitalExpansion::Title, text),
+            "Aerø, Caesar, AeRØ, AeOe"
+        );
+        assert_eq!(
+            fold(CapitalExpansion::FollowContext, text),
+            "Aerø, Caesar, AERØ, AEOE"
+        );
+        assert_eq!(
+            fold(CapitalExpansion::Upper, text),
+            "AErø, Caesar, AERØ, AEOE"
+        );
+        // like 'Ä'
+        assert_eq!(
+            fold(CapitalExpansion::Title, "ÄRØ"),
+            fold(CapitalExpansion::Title, "ÆRØ")
+        );
+        let folded = Transliterator::new().fold_ascii("Lüÿt, L'HAŸ, Noël".as_bytes());
+        assert_eq!(folded, "Lueyt, L'HAY, Noël".as_bytes());
+    }
+
+    #[cfg(not(feature = "latin"))]
+    #[test]
+    fn fold_ascii_without_latin() {
+        let folded = Transliterator::new().fold_ascii("Cæsar".as_bytes());
+        assert_eq!(folded, "Cæsar".as_bytes());
+    }
+
+    #[test]
+    fn uppercase_umlauts() {
+        let upper = Transliterator::new().uppercase("Öl Ärmel übermut café".as_bytes());
+        assert_eq!(upper, "ÖL ÄRMEL ÜBERMUT CAFé".as_bytes());
+        assert_eq!(Transliterator::new().uppercase(&[b'a', 0xc3]), [b'A', 0xc3]);
+    }
+}
@@ -0,0 +1,94 @@
+//! Umlauts written as ASCII input sequences.
+use crate::fold::next_umlaut;
+use crate::Umlaut;
+
+/// How umlauts are typed on a keyboard without umlauts.
+///
+/// Used by [`UmlautsOwned::to_typeable_ascii`](crate::UmlautsOwned::to_typeable_ascii).
+/// Unlike the DIN 5007 folds, the conventions are meant as input notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeConvention {
+    /// A '"' before the base letter, like the dead key of the US International layout
+    /// and the `babel` package of LaTeX: "ä" -> "\"a", "Ä" -> "\"A", "ß" -> "\"s", "ẞ" -> "\"S".
+    DoubleQuotePrefix,
+    /// The DIN 5007-2 replacement: "ä" -> "ae", "Ä" -> "Ae", "ß" -> "ss", "ẞ" -> "SS".
+    DinAe,
+}
+
+impl TypeConvention {
+    /// Returns the ASCII sequence used to type `umlaut`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::{TypeConvention, Umlaut};
+    ///
+    /// assert_eq!(TypeConvention::DoubleQuotePrefix.sequence(Umlaut::UppercaseO), b"\"O");
+    /// assert_eq!(TypeConvention::DinAe.sequence(Umlaut::UppercaseO), b"Oe");
+    /// ```
+    pub fn sequence(self, umlaut: Umlaut) -> &'static [u8] {
+        match self {
+            TypeConvention::DoubleQuotePrefix => match umlaut {
+                Umlaut::LowercaseA => b"\"a",
+                Umlaut::LowercaseO => b"\"o",
+                Umlaut::LowercaseU => b"\"u",
+                Umlaut::UppercaseA => b"\"A",
+                Umlaut::UppercaseO => b"\"O",
+                Umlaut::UppercaseU => b"\"U",
+                Umlaut::LowercaseSharpS => b"\"s",
+                Umlaut::UppercaseSharpS => b"\"S",
+            },
+            TypeConvention::DinAe => umlaut.din_5007_2(),
+        }
+    }
+}
+
+/// Replaces every umlaut in `bytes` by its sequence in `convention`.
+///
+/// See [`UmlautsOwned::to_typeable_ascii`](crate::UmlautsOwned::to_typeable_ascii).
+pub(crate) fn to_typeable(bytes: &[u8], convention: TypeConvention) -> Vec<u8> {
+    let mut typeable = Vec::with_capacity(bytes.len());
+    let mut last = 0;
+    while let Some((range, _)) = next_umlaut(bytes, last) {
+        typeable.extend_from_slice(&bytes[last..range.start]);
+        if let Some(umlaut) = Umlaut::from_bytes(&bytes[range.start..]) {
+            typeable.extend_from_slice(convention.sequence(umlaut));
+        }
+        last = range.end;
+    }
+    typeable.extend_from_slice(&bytes[last..]);
+    typeable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_typeable, TypeConvention};
+
+    #[test]
+    fn double_quote_prefix() {
+        assert_eq!(
+            to_typeable(
+                "Grüße aus Öhringen".as_bytes(),
+                TypeConvention::DoubleQuotePrefix
+            ),
+            b"Gr\"u\"se aus \"Ohringen"
+        );
+        assert_eq!(
+            to_typeable("ÄÖÜäöüßẞ".as_bytes(), TypeConvention::DoubleQuotePrefix),
+            b"\"A\"O\"U\"a\"o\"u\"s\"S"
+        );
+    }
+
+    #[test]
+    fn din_ae() {
+        assert_eq!(
+            to_typeable("Grüße aus Öhringen".as_bytes(), TypeConvention::DinAe),
+            b"Gruesse aus Oehringen"
+        );
+        assert_eq!(
+            to_typeable("café".as_bytes(), TypeConvention::DinAe),
+            "café".as_bytes()
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Classification of umlauts.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// One of the german special characters "äöüÄÖÜßẞ".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Umlaut {
+    /// 'ä'
+    LowercaseA,
+    /// 'ö'
+    LowercaseO,
+    /// 'ü'
+    LowercaseU,
+    /// 'Ä'
+    UppercaseA,
+    /// 'Ö'
+    UppercaseO,
+    /// 'Ü'
+    UppercaseU,
+    /// 'ß'
+    LowercaseSharpS,
+    /// 'ẞ'
+    UppercaseSharpS,
+}
+
+impl Umlaut {
+    /// Classifies the umlaut at the start of `bytes`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::Umlaut;
+    ///
+    /// assert_eq!(Umlaut::from_bytes("ärger".as_bytes()), Some(Umlaut::LowercaseA));
+    /// assert_eq!(Umlaut::from_bytes(b"aerger"), None);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Option<Umlaut> {
+        match bytes {
+            [0xc3, 0xa4, ..] => Some(Umlaut::LowercaseA),
+            [0xc3, 0xb6, ..] => Some(Umlaut::LowercaseO),
+            [0xc3, 0xbc, ..] => Some(Umlaut::LowercaseU),
+            [0xc3, 0x84, ..] => Some(Umlaut::UppercaseA),
+            [0xc3, 0x96, ..] => Some(Umlaut::UppercaseO),
+            [0xc3, 0x9c, ..] => Some(Umlaut::UppercaseU),
+            [0xc3, 0x9f, ..] => Some(Umlaut::LowercaseSharpS),
+            [0xe1, 0xba, 0x9e, ..] => Some(Umlaut::UppercaseSharpS),
+            _ => None,
+        }
+    }
+
+    /// Returns the replacement according to DIN 5007-2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::Umlaut;
+    ///
+    /// assert_eq!(Umlaut::UppercaseA.din_5007_2(), b"Ae");
+    /// assert_eq!(Umlaut::UppercaseSharpS.din_5007_2(), b"SS");
+    /// ```
+    pub fn din_5007_2(self) -> &'static [u8] {
+        match self {
+            Umlaut::LowercaseA => b"ae",
+            Umlaut::LowercaseO => b"oe",
+            Umlaut::LowercaseU => b"ue",
+            Umlaut::UppercaseA => b"Ae",
+            Umlaut::UppercaseO => b"Oe",
+            Umlaut::UppercaseU => b"Ue",
+            Umlaut::LowercaseSharpS => b"ss",
+            Umlaut::UppercaseSharpS => b"SS",
+        }
+    }
+
+    /// Returns the umlaut as a `char`.
+    pub fn as_char(self) -> char {
+        match self {
+            Umlaut::LowercaseA => 'ä',
+            Umlaut::LowercaseO => 'ö',
+            Umlaut::LowercaseU => 'ü',
+            Umlaut::UppercaseA => 'Ä',
+            Umlaut::UppercaseO => 'Ö',
+            Umlaut::UppercaseU => 'Ü',
+            Umlaut::LowercaseSharpS => 'ß',
+            Umlaut::UppercaseSharpS => 'ẞ',
+        }
+    }
+}
+
+/// Formats the umlaut as its character.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::Umlaut;
+///
+/// assert_eq!(Umlaut::UppercaseSharpS.to_string(), "ẞ");
+/// ```
+impl fmt::Display for Umlaut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut buf = [0; 4];
+        f.pad(self.as_char().encode_utf8(&mut buf))
+    }
+}
+
+/// Error returned by parsing an [`Umlaut`] from a string that is not a single umlaut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseUmlautError;
+
+impl fmt::Display for ParseUmlautError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("string is not a single umlaut")
+    }
+}
+
+impl Error for ParseUmlautError {}
+
+/// Parses a string consisting of exactly one umlaut character.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate umlauts;
+/// use umlauts::Umlaut;
+///
+/// assert_eq!("ä".parse(), Ok(Umlaut::LowercaseA));
+/// assert!("ae".parse::<Umlaut>().is_err());
+/// ```
+impl FromStr for Umlaut {
+    type Err = ParseUmlautError;
+
+    fn from_str(s: &str) -> Result<Umlaut, ParseUmlautError> {
+        match Umlaut::from_bytes(s.as_bytes()) {
+            Some(umlaut) if umlaut.as_char().len_utf8() == s.len() => Ok(umlaut),
+            _ => Err(ParseUmlautError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseUmlautError, Umlaut};
+    use crate::fold::din_5007_2_at;
+
+    #[test]
+    fn din_5007_2_matches_scan() {
+        for c in "äöüÄÖÜßẞ".chars() {
+            let mut buf = [0; 4];
+            let bytes = c.encode_utf8(&mut buf).as_bytes();
+            let (_, replacement) = din_5007_2_at(bytes).unwrap();
+            assert_eq!(Umlaut::from_bytes(bytes).unwrap().din_5007_2(), replacement);
+        }
+    }
+
+    #[test]
+    fn from_bytes_as_char() {
+        for c in "äöüÄÖÜßẞ".chars() {
+            let mut buf = [0; 4];
+            let umlaut = Umlaut::from_bytes(c.encode_utf8(&mut buf).as_bytes()).unwrap();
+            assert_eq!(umlaut.as_char(), c);
+        }
+        assert_eq!(Umlaut::from_bytes("é".as_bytes()), None);
+        assert_eq!(Umlaut::from_bytes(&[0xc3]), None);
+        assert_eq!(Umlaut::from_bytes(b""), None);
+    }
+
+    #[test]
+    fn display_parse_round_trip() {
+        for c in "äöüÄÖÜßẞ".chars() {
+            let mut buf = [0; 4];
+            let umlaut = Umlaut::from_bytes(c.encode_utf8(&mut buf).as_bytes()).unwrap();
+            assert_eq!(umlaut.to_string(), c.to_string());
+            assert_eq!(umlaut.to_string().parse(), Ok(umlaut));
+        }
+        assert_eq!(format!("[{:>3}]", Umlaut::LowercaseU), "[  ü]");
+    }
+
+    #[test]
+    fn parse_errors() {
+        for s in &["", "a", "ae", "ää", "äx", "é", "\u{c3}"] {
+            assert_eq!(s.parse::<Umlaut>(), Err(ParseUmlautError), "{:?}", s);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! UTF-8 decoding helpers for byte slices that are not guaranteed to be valid.
+
+/// Returns the length of the UTF-8 sequence starting with `lead`.
+///
+/// Returns 1 for continuation bytes and bytes that can never start a sequence.
+pub(crate) fn sequence_len(lead: u8) -> usize {
+    match lead {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => 1,
+    }
+}
+
+/// Decodes the character at the start of `bytes`.
+///
+/// Returns the character and its length in bytes,
+/// or `None` if `bytes` is empty or does not start with a valid UTF-8 sequence.
+pub(crate) fn decode_char(bytes: &[u8]) -> Option<(char, usize)> {
+    let len = sequence_len(*bytes.first()?);
+    let c = std::str::from_utf8(bytes.get(..len)?)
+        .ok()?
+        .chars()
+        .next()?;
+    Some((c, len))
+}
+
+/// Decodes the character at the end of `bytes`.
+///
+/// Returns `None` if `bytes` is empty or does not end with a valid UTF-8 sequence.
+pub(crate) fn decode_last_char(bytes: &[u8]) -> Option<char> {
+    let start = bytes
+        .iter()
+        .rposition(|&byte| byte & 0xc0 != 0x80)
+        .filter(|&start| bytes.len() - start <= 4)?;
+    match decode_char(&bytes[start..]) {
+        Some((c, len)) if start + len == bytes.len() => Some(c),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_char, decode_last_char};
+
+    #[test]
+    fn decode_last() {
+        assert_eq!(decode_last_char(b"abc"), Some('c'));
+        assert_eq!(decode_last_char("aä".as_bytes()), Some('ä'));
+        assert_eq!(decode_last_char("„".as_bytes()), Some('„'));
+        assert_eq!(decode_last_char("🦀".as_bytes()), Some('🦀'));
+        assert_eq!(decode_last_char(b""), None);
+        assert_eq!(decode_last_char(&[b'a', 0xc3]), None);
+        assert_eq!(decode_last_char(&[b'a', 0xa4]), None);
+    }
+
+    #[test]
+    fn decode() {
+        assert_eq!(decode_char(b"abc"), Some(('a', 1)));
+        assert_eq!(decode_char("äb".as_bytes()), Some(('ä', 2)));
+        assert_eq!(decode_char("ẞ".as_bytes()), Some(('ẞ', 3)));
+        assert_eq!(decode_char("🦀".as_bytes()), Some(('🦀', 4)));
+        assert_eq!(decode_char(b""), None);
+        assert_eq!(decode_char(&[0xc3]), None);
+        assert_eq!(decode_char(&[0xa4, b'a']), None);
+    }
+}
@@ -0,0 +1,312 @@
+use crate::fold::{fold_din_5007_2, next_umlaut};
+use std::ops::Range;
+
+/// Resizing string processing functions for `Vec<u8>`.
+///
+/// `UmlautsVecExt` adds functions for the german "Umlauts" that work inplace like
+/// [`UmlautsInplaceExt`](crate::UmlautsInplaceExt), but may change the length of the `Vec`.
+/// This allows handling all umlauts including the uppercase ß without
+/// allocating a second buffer.
+pub trait UmlautsVecExt {
+    /// Converts Umlauts to ae, oe, ue, ss, ... reusing the buffer.
+    ///
+    /// Maps umlauts according to DIN 5007-2 like
+    /// [`make_utf8_umlauts_to_ascii`](crate::UmlautsInplaceExt::make_utf8_umlauts_to_ascii),
+    /// but also maps the uppercase ß ('ẞ' -> 'SS') by shifting the following bytes.
+    /// As no replacement is longer than its umlaut, this never reallocates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "GROẞE Grüße".as_bytes().to_vec();
+    /// s.fold_ascii_reusing_capacity();
+    /// assert_eq!(s, b"GROSSE Gruesse");
+    /// ```
+    fn fold_ascii_reusing_capacity(&mut self);
+
+    /// Collapses runs of four or more identical vowels to two.
+    ///
+    /// Intended as a cleanup step for folded text, where runs like "aaaa" can't be
+    /// a legitimate german spelling. The vowels are compared ignoring case and the
+    /// first two of a run are kept.
+    /// As compounds like "Kaffeeersatz", "Zooorchester" or "Hawaiiinsel" are written
+    /// with three identical vowels since the 1996 reform, a run of exactly three is left
+    /// unchanged.
+    ///
+    /// Returns the number of removed bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = b"Aaaal im Kaffeeersatz, Ooooops".to_vec();
+    /// assert_eq!(s.dedupe_folded_vowels_in_place(), 5);
+    /// assert_eq!(s, b"Aal im Kaffeeersatz, Oops");
+    /// ```
+    fn dedupe_folded_vowels_in_place(&mut self) -> usize;
+
+    /// Converts Umlauts to ae, oe, ue, ss, ... only within `range`.
+    ///
+    /// Maps umlauts according to DIN 5007-2 including the uppercase ß ('ẞ' -> 'SS')
+    /// and replaces the range with the result. The bytes after the range are shifted
+    /// if the length changed, the bytes before it are untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or if its start or end
+    /// is not on a UTF-8 character boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut s = "Öl und GROẞE Grüße".as_bytes().to_vec();
+    /// s.fold_ascii_range(7..15);
+    /// assert_eq!(s, "Öl und GROSSE Grüße".as_bytes());
+    /// ```
+    fn fold_ascii_range(&mut self, range: Range<usize>);
+
+    /// Removes and returns an umlaut truncated at the end of the buffer.
+    ///
+    /// If the buffer ends with a lone lead byte `0xc3` of 'ä', 'ö', 'ü', 'Ä', 'Ö', 'Ü' or 'ß',
+    /// or with the first one or two bytes of 'ẞ', these bytes are removed and returned,
+    /// so they can be prepended to the next chunk.
+    /// Otherwise the buffer is left unchanged and `None` is returned.
+    ///
+    /// The returned bytes have to be a `Vec`, as a truncated 'ẞ' may consist of two bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate umlauts;
+    /// use umlauts::prelude::*;
+    ///
+    /// let mut chunk = vec![b'G', b'r', 0xc3];
+    /// let rest = chunk.repair_trailing_umlaut();
+    /// assert_eq!(chunk, b"Gr");
+    /// assert_eq!(rest, Some(vec![0xc3]));
+    ///
+    /// let mut chunk = "Grüße".as_bytes().to_vec();
+    /// assert_eq!(chunk.repair_trailing_umlaut(), None);
+    /// ```
+    fn repair_trailing_umlaut(&mut self) -> Option<Vec<u8>>;
+}
+
+impl UmlautsVecExt for Vec<u8> {
+    fn fold_ascii_reusing_capacity(&mut self) {
+        let len = self.len();
+        let mut written = 0;
+        let mut last = 0;
+        while let Some((range, replacement)) = next_umlaut(self, last) {
+            debug_assert!(replacement.len() <= range.len());
+            self.copy_within(last..range.start, written);
+            written += range.start - last;
+            self[written..written + replacement.len()].copy_from_slice(replacement);
+            written += replacement.len();
+            last = range.end;
+        }
+        self.copy_within(last..len, written);
+        written += len - last;
+        self.truncate(written);
+    }
+
+    fn dedupe_folded_vowels_in_place(&mut self) -> usize {
+        let len = self.len();
+        let mut written = 0;
+        let mut i = 0;
+        while i < len {
+            let lower = self[i].to_ascii_lowercase();
+            let run = self[i..]
+                .iter()
+                .take_while(|byte| byte.to_ascii_lowercase() == lower)
+                .count();
+            let keep = match (lower, run) {
+                (b'a' | b'e' | b'i' | b'o' | b'u', _) if run >= 4 => 2,
+                _ => run,
+            };
+            self.copy_within(i..i + keep, written);
+            written += keep;
+            i += run;
+        }
+        self.truncate(written);
+        len - written
+    }
+
+    fn fold_ascii_range(&mut self, range: Range<usize>) {
+        let is_boundary = |i: usize| {
+            self.get(i)
+                .map_or(i == self.len(), |byte| byte & 0xc0 != 0x80)
+        };
+        assert!(
+            range.start <= range.end && is_boundary(range.start) && is_boundary(range.end),
+            "range {}..{} is out of bounds or not on character boundaries",
+            range.start,
+            range.end
+        );
+        let mut folded = Vec::with_capacity(range.len());
+        fold_din_5007_2(&self[range.clone()], &mut folded);
+        self.splice(range, folded);
+    }
+
+    fn repair_trailing_umlaut(&mut self) -> Option<Vec<u8>> {
+        let truncated = match self.as_slice() {
+            // ẞ
+            [.., 0xe1, 0xba] => 2,
+            [.., 0xc3] | [.., 0xe1] => 1,
+            _ => return None,
+        };
+        Some(self.split_off(self.len() - truncated))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{UmlautsOwned, UmlautsVecExt};
+
+    #[test]
+    fn fold_ascii_reusing_capacity_no_reallocation() {
+        let mut text = "Öl Ärmel Übermut, Straße".as_bytes().to_vec();
+        let (ptr, capacity) = (text.as_ptr(), text.capacity());
+        text.fold_ascii_reusing_capacity();
+        assert_eq!(text, b"Oel Aermel Uebermut, Strasse");
+        assert_eq!(text.as_ptr(), ptr);
+        assert_eq!(text.capacity(), capacity);
+    }
+
+    #[test]
+    fn fold_ascii_reusing_capacity_shrinks() {
+        let mut text = "ẞẞ GROẞE é".as_bytes().to_vec();
+        let ptr = text.as_ptr();
+        text.fold_ascii_reusing_capacity();
+        assert_eq!(text, "SSSS GROSSE é".as_bytes());
+        assert_eq!(text.as_ptr(), ptr);
+        let mut text = "ÄÖÜäöüßẞ".as_bytes().to_vec();
+        text.fold_ascii_reusing_capacity();
+        assert_eq!(text.ensure_folded_ascii(), Ok(()));
+    }
+
+    #[test]
+    fn dedupe_folded_vowels() {
+        let mut text = "Mäe Muuuuh Eeeeis".as_bytes().to_vec();
+        text.fold_ascii_reusing_capacity();
+        assert_eq!(text, b"Maee Muuuuh Eeeeis");
+        assert_eq!(text.dedupe_folded_vowels_in_place(), 4);
+        assert_eq!(text, b"Maee Muuh Eeis");
+    }
+
+    #[test]
+    fn dedupe_folded_vowels_keeps_german_spellings() {
+        for word in &[
+            "Seeelefant",
+            "Kaffeeersatz",
+            "Teeei",
+            "Zooorchester",
+            "Hawaiiinsel",
+            "Schifffahrt",
+            "Aal",
+            "Moor",
+            "Beet",
+            "Vakuum",
+            "",
+        ] {
+            let mut text = word.as_bytes().to_vec();
+            assert_eq!(text.dedupe_folded_vowels_in_place(), 0);
+            assert_eq!(text, word.as_bytes());
+        }
+    }
+
+    #[test]
+    fn fold_ascii_range() {
+        let text = "Müller, GROẞE Straße, Öl";
+        let start = text.find("GROẞE").unwrap();
+        let end = text.find(", Öl").unwrap();
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.fold_ascii_range(start..end);
+        assert_eq!(bytes, "Müller, GROSSE Strasse, Öl".as_bytes());
+        // the following text is shifted, as 'ẞ' has three bytes and "SS" only two
+        assert_eq!(bytes.len(), text.len() - 1);
+        assert_eq!(&bytes[end - 1..], ", Öl".as_bytes());
+        assert_eq!(&bytes[..start], "Müller, ".as_bytes());
+        let mut bytes = "ÄÖÜäöüßẞ".as_bytes().to_vec();
+        bytes.fold_ascii_range(0..bytes.len());
+        assert_eq!(bytes.ensure_folded_ascii(), Ok(()));
+
+        let mut bytes = "ẞẞ".as_bytes().to_vec();
+        bytes.fold_ascii_range(3..6);
+        assert_eq!(bytes, "ẞSS".as_bytes());
+        bytes.fold_ascii_range(0..0);
+        bytes.fold_ascii_range(5..5);
+        assert_eq!(bytes, "ẞSS".as_bytes());
+    }
+
+    #[test]
+    #[should_panic(expected = "not on character boundaries")]
+    fn fold_ascii_range_inside_character() {
+        let mut bytes = "Grüße".as_bytes().to_vec();
+        bytes.fold_ascii_range(0..3);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn fold_ascii_range_out_of_bounds() {
+        let mut bytes = b"abc".to_vec();
+        bytes.fold_ascii_range(1..4);
+    }
+
+    #[test]
+    fn repair_trailing_umlaut() {
+        let mut text = vec![b'a', 0xc3];
+        assert_eq!(text.repair_trailing_umlaut(), Some(vec![0xc3]));
+        assert_eq!(text, b"a");
+        assert_eq!(text.repair_trailing_umlaut(), None);
+        assert_eq!(text, b"a");
+
+        let text = "GROẞ".as_bytes();
+        for len in 0..text.len() {
+            let mut chunk = text[..len].to_vec();
+            let rest = chunk.repair_trailing_umlaut().unwrap_or_default();
+            assert_eq!(chunk, &text[..len.min(3)]);
+            chunk.extend_from_slice(&rest);
+            assert_eq!(chunk, &text[..len]);
+        }
+        let mut text = vec![0xc3];
+        assert_eq!(text.repair_trailing_umlaut(), Some(vec![0xc3]));
+        assert!(text.is_empty());
+        assert_eq!(Vec::new().repair_trailing_umlaut(), None);
+    }
+
+    #[test]
+    fn repair_trailing_umlaut_chunks() {
+        use crate::UmlautsInplaceExt;
+
+        let text = "Öl Ärmel Übermut Grüße".as_bytes();
+        let mut folded = Vec::new();
+        let mut rest = Vec::new();
+        for chunk in text.chunks(3) {
+            let mut buf = std::mem::take(&mut rest);
+            buf.extend_from_slice(chunk);
+            rest = buf.repair_trailing_umlaut().unwrap_or_default();
+            buf.make_utf8_umlauts_to_ascii();
+            folded.extend_from_slice(&buf);
+        }
+        assert!(rest.is_empty());
+        assert_eq!(folded, b"Oel Aermel Uebermut Gruesse");
+    }
+
+    #[test]
+    fn fold_ascii_reusing_capacity_edge_cases() {
+        let mut text = Vec::new();
+        text.fold_ascii_reusing_capacity();
+        assert!(text.is_empty());
+        let mut text = vec![b'a', 0xc3];
+        text.fold_ascii_reusing_capacity();
+        assert_eq!(text, [b'a', 0xc3]);
+    }
+}